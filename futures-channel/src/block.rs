@@ -0,0 +1,41 @@
+//! Blocking the current OS thread on a channel operation.
+//!
+//! This is a minimal version of the `block_on` loop of `futures-executor`, so
+//! the blocking channel methods don't need to depend on an executor.
+
+use futures_core::task::{self, LocalWaker, Poll, Wake};
+use std::sync::Arc;
+use std::thread::{self, Thread};
+
+struct ThreadNotify {
+    thread: Thread,
+}
+
+thread_local! {
+    static CURRENT_THREAD_NOTIFY: Arc<ThreadNotify> = Arc::new(ThreadNotify {
+        thread: thread::current(),
+    });
+}
+
+impl Wake for ThreadNotify {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.thread.unpark();
+    }
+}
+
+// Invokes `f` until it returns `Ready`, parking the current thread in
+// between until `f`'s waker is woken.
+pub(crate) fn block_on_poll<T, F>(mut f: F) -> T
+    where F: FnMut(&LocalWaker) -> Poll<T>,
+{
+    CURRENT_THREAD_NOTIFY.with(|thread_notify| {
+        let local_waker =
+            task::local_waker_from_nonlocal(thread_notify.clone());
+        loop {
+            if let Poll::Ready(t) = f(&local_waker) {
+                return t;
+            }
+            thread::park();
+        }
+    })
+}
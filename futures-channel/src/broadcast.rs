@@ -0,0 +1,445 @@
+//! A multi-producer, multi-consumer channel where every receiver sees every
+//! message.
+//!
+//! Channel creation provides a [`Sender`] and a [`Receiver`] handle. Both can
+//! be cloned, and [`Sender::subscribe`] creates additional receivers. Every
+//! message sent is delivered to all receivers which exist at the time it is
+//! sent, so the message type has to implement `Clone`.
+//!
+//! # Lagging
+//!
+//! The channel holds at most `capacity` messages. Senders never wait for
+//! receivers: once the buffer is full, sending a message drops the oldest
+//! one. A receiver which hadn't received the dropped messages yet observes a
+//! [`Lagged`] error with the number of messages it missed, and then continues
+//! with the oldest message which is still buffered.
+//!
+//! # Disconnection
+//!
+//! Once all [`Sender`] handles have been dropped, or one of them called
+//! [`Sender::close_channel`], receivers yield the messages which are still
+//! buffered for them and then terminate. Sending fails once all [`Receiver`]
+//! handles have been dropped or the channel has been closed.
+
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll, Waker};
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Creates a new broadcast channel which buffers up to `capacity` messages,
+/// returning the sender/receiver halves.
+///
+/// Further receivers can be created with [`Sender::subscribe`] or by cloning
+/// the [`Receiver`].
+///
+/// # Panics
+///
+/// This function panics if `capacity` is zero.
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast capacity must be greater than zero");
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(capacity),
+            head: 0,
+            capacity,
+            num_senders: 1,
+            num_receivers: 0,
+            closed: false,
+            next_id: 0,
+            wakers: HashMap::new(),
+        }),
+    });
+
+    let rx = Receiver::new(shared.clone(), |state| state.tail());
+    (Sender { shared }, rx)
+}
+
+/// The transmission end of a broadcast channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving end of a broadcast channel.
+///
+/// This value is created by the [`channel`](channel) function or by
+/// [`Sender::subscribe`].
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    // Sequence number of the next message this receiver will yield.
+    pos: u64,
+    id: usize,
+    terminated: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    buffer: VecDeque<Slot<T>>,
+    // Sequence number of the first message in `buffer`.
+    head: u64,
+    capacity: usize,
+    num_senders: usize,
+    num_receivers: usize,
+    // Set by `Sender::close_channel`.
+    closed: bool,
+    next_id: usize,
+    // Wakers of receivers waiting for a message, by receiver id.
+    wakers: HashMap<usize, Waker>,
+}
+
+struct Slot<T> {
+    value: T,
+    // Number of receivers which haven't received this message yet.
+    remaining: usize,
+}
+
+/// The error type returned from [`Sender::send`] when there are no
+/// receivers left or the channel has been closed.
+///
+/// The message which couldn't be sent is returned in the error.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// Error yielded by a [`Receiver`] which fell so far behind that messages
+/// were dropped before it could receive them.
+///
+/// The contained number is the count of messages the receiver missed. The
+/// receiver continues with the oldest message which is still buffered.
+///
+/// This is also the error yielded by lagging subscribers of the
+/// `futures_util` `Broadcast` stream combinator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+/// The error type returned from [`Receiver::try_recv`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// There is no message available right now.
+    Empty,
+    /// The receiver missed the contained number of messages, see [`Lagged`].
+    Lagged(u64),
+    /// All senders have been dropped and no messages are left.
+    Closed,
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("SendError")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "send failed because there are no receivers")
+    }
+}
+
+impl<T: Any> Error for SendError<T> {
+    fn description(&self) -> &str {
+        "send failed because there are no receivers"
+    }
+}
+
+impl<T> SendError<T> {
+    /// Returns the message that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "receiver lagged behind by {} messages", self.0)
+    }
+}
+
+impl Error for Lagged {
+    fn description(&self) -> &str {
+        "receiver lagged behind"
+    }
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(fmt, "receiver channel is empty"),
+            TryRecvError::Lagged(n) => {
+                write!(fmt, "receiver lagged behind by {} messages", n)
+            }
+            TryRecvError::Closed => write!(fmt, "receiver channel is closed"),
+        }
+    }
+}
+
+impl Error for TryRecvError {
+    fn description(&self) -> &str {
+        match self {
+            TryRecvError::Empty => "receiver channel is empty",
+            TryRecvError::Lagged(_) => "receiver lagged behind",
+            TryRecvError::Closed => "receiver channel is closed",
+        }
+    }
+}
+
+impl<T> Shared<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    // Sequence number of the next message to be sent.
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+
+    // Drops buffered messages that every receiver has already received.
+    fn trim(&mut self) {
+        while self.buffer.front().map_or(false, |slot| slot.remaining == 0) {
+            self.buffer.pop_front();
+            self.head += 1;
+        }
+    }
+
+    fn wake_all(&mut self) {
+        for (_, waker) in self.wakers.drain() {
+            waker.wake();
+        }
+    }
+}
+
+/*
+ *
+ * ===== impl Sender =====
+ *
+ */
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers.
+    ///
+    /// This method never waits: if the channel is full, the oldest message
+    /// is dropped to make room, and receivers which haven't received it yet
+    /// will observe a [`Lagged`] error.
+    ///
+    /// An error is returned if there are no receivers or the channel has
+    /// been closed, in which case the message is handed back.
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        if state.num_receivers == 0 || state.closed {
+            return Err(SendError(msg));
+        }
+
+        if state.buffer.len() == state.capacity {
+            state.buffer.pop_front();
+            state.head += 1;
+        }
+        let remaining = state.num_receivers;
+        state.buffer.push_back(Slot { value: msg, remaining });
+        state.wake_all();
+        Ok(())
+    }
+
+    /// Creates a new receiver which receives every message sent from now
+    /// on.
+    ///
+    /// Messages which are already buffered aren't delivered to the new
+    /// receiver.
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver::new(self.shared.clone(), |state| state.tail())
+    }
+
+    /// Closes the channel for all senders.
+    ///
+    /// Receivers still yield the messages which are already buffered for
+    /// them, and then terminate. Any further [`send`](Sender::send) fails.
+    pub fn close_channel(&self) {
+        let mut state = self.shared.lock();
+        state.closed = true;
+        state.wake_all();
+    }
+
+    /// Returns whether the channel has been closed, either by
+    /// [`close_channel`](Sender::close_channel) or because all receivers
+    /// have been dropped.
+    pub fn is_closed(&self) -> bool {
+        let state = self.shared.lock();
+        state.closed || state.num_receivers == 0
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.shared.lock().num_senders += 1;
+        Sender { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            // Let waiting receivers observe the end of the stream.
+            state.wake_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender")
+            .finish()
+    }
+}
+
+/*
+ *
+ * ===== impl Receiver =====
+ *
+ */
+
+impl<T> Receiver<T> {
+    fn new<F>(shared: Arc<Shared<T>>, pos: F) -> Receiver<T>
+        where F: FnOnce(&mut State<T>) -> u64,
+    {
+        let (pos, id) = {
+            let mut state = shared.lock();
+            let pos = pos(&mut state);
+            let head = state.head;
+            let start = pos.saturating_sub(head) as usize;
+            for slot in state.buffer.iter_mut().skip(start) {
+                slot.remaining += 1;
+            }
+            state.num_receivers += 1;
+            state.next_id += 1;
+            (pos, state.next_id)
+        };
+        Receiver { shared, pos, id, terminated: false }
+    }
+
+    fn next_message(&mut self, state: &mut State<T>) -> Poll<Option<Result<T, Lagged>>>
+        where T: Clone,
+    {
+        if self.pos < state.head {
+            let missed = state.head - self.pos;
+            self.pos = state.head;
+            return Poll::Ready(Some(Err(Lagged(missed))));
+        }
+
+        let index = (self.pos - state.head) as usize;
+        if index < state.buffer.len() {
+            self.pos += 1;
+            let slot = &mut state.buffer[index];
+            slot.remaining -= 1;
+            let msg = if index == 0 && slot.remaining == 0 {
+                // We were the last receiver of this message, so it can be
+                // moved out instead of cloned.
+                state.head += 1;
+                let slot = state.buffer.pop_front().unwrap();
+                state.trim();
+                slot.value
+            } else {
+                slot.value.clone()
+            };
+            return Poll::Ready(Some(Ok(msg)));
+        }
+
+        if state.num_senders == 0 || state.closed {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+
+    /// Tries to receive the next message without registering the current
+    /// task for a wakeup.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError>
+        where T: Clone,
+    {
+        let shared = self.shared.clone();
+        let mut state = shared.lock();
+        match self.next_message(&mut state) {
+            Poll::Ready(Some(Ok(msg))) => Ok(msg),
+            Poll::Ready(Some(Err(Lagged(n)))) => Err(TryRecvError::Lagged(n)),
+            Poll::Ready(None) => Err(TryRecvError::Closed),
+            Poll::Pending => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    /// Creates a new receiver which starts at the same position as this one,
+    /// so it receives the same messages from now on.
+    fn clone(&self) -> Receiver<T> {
+        let pos = self.pos;
+        let mut rx = Receiver::new(self.shared.clone(), |_| pos);
+        rx.terminated = self.terminated;
+        rx
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        let shared = self.shared.clone();
+        let mut state = shared.lock();
+        match self.next_message(&mut state) {
+            Poll::Ready(msg) => {
+                if msg.is_none() {
+                    self.terminated = true;
+                }
+                Poll::Ready(msg)
+            }
+            Poll::Pending => {
+                state.wakers.insert(self.id, lw.clone().into_waker());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        let head = state.head;
+        let start = self.pos.saturating_sub(head) as usize;
+        for slot in state.buffer.iter_mut().skip(start) {
+            slot.remaining -= 1;
+        }
+        state.trim();
+        state.num_receivers -= 1;
+        state.wakers.remove(&self.id);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver")
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
@@ -2,8 +2,14 @@
 //!
 //! This crate provides channels that can be used to communicate between
 //! asynchronous tasks.
+//!
+//! Without the default `std` feature, the [`oneshot`] and [`mpsc`] channels
+//! are still available with the `alloc` feature. Their blocking methods and
+//! the exact-capacity [`mpsc::bounded`] channel require `std`, like all of
+//! the other channels.
 
 #![feature(futures_api)]
+#![cfg_attr(feature = "alloc", feature(alloc))]
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -12,9 +18,24 @@
 
 #![doc(html_root_url = "https://rust-lang-nursery.github.io/futures-api-docs/0.3.0-alpha.12/futures_channel")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "std")]
+mod block;
+#[cfg(feature = "std")]
+pub mod broadcast;
+#[cfg(feature = "alloc")]
 mod lock;
 #[cfg(feature = "std")]
+pub mod mpmc;
+#[cfg(feature = "alloc")]
 pub mod mpsc;
-#[cfg(feature = "std")]
+#[cfg(feature = "alloc")]
 pub mod oneshot;
+#[cfg(feature = "std")]
+pub mod priority;
+#[cfg(feature = "std")]
+mod waiters;
+#[cfg(feature = "std")]
+pub mod watch;
@@ -1,18 +1,20 @@
-//! A "mutex" which only supports `try_lock`
+//! A "mutex" which mostly supports `try_lock`
 //!
 //! As a futures library the eventual call to an event loop should be the only
 //! thing that ever blocks, so this is assisted with a fast user-space
-//! implementation of a lock that can only have a `try_lock` operation.
+//! implementation of a lock that mostly has a `try_lock` operation. A spinning
+//! `lock` is available for very short critical sections, which also makes it
+//! usable without `std`.
 
 use core::cell::UnsafeCell;
 use core::ops::{Deref, DerefMut};
 use core::sync::atomic::Ordering::SeqCst;
-use core::sync::atomic::AtomicBool;
+use core::sync::atomic::{spin_loop_hint, AtomicBool};
 
 /// A "mutex" around a value, similar to `std::sync::Mutex<T>`.
 ///
-/// This lock only supports the `try_lock` operation, however, and does not
-/// implement poisoning.
+/// This lock only supports the `try_lock` operation and a spinning `lock`,
+/// however, and does not implement poisoning.
 #[derive(Debug)]
 pub(crate) struct Lock<T> {
     locked: AtomicBool,
@@ -59,6 +61,20 @@ impl<T> Lock<T> {
             None
         }
     }
+
+    /// Acquires this lock, spinning until it is released if it is already
+    /// locked.
+    ///
+    /// This must only be used for critical sections which are known to be
+    /// very short, and never while this thread might already hold the lock.
+    pub(crate) fn lock(&self) -> TryLock<'_, T> {
+        loop {
+            if let Some(guard) = self.try_lock() {
+                return guard;
+            }
+            spin_loop_hint();
+        }
+    }
 }
 
 impl<'a, T> Deref for TryLock<'a, T> {
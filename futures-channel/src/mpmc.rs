@@ -0,0 +1,350 @@
+//! A multi-producer, multi-consumer queue for distributing values across
+//! asynchronous tasks.
+//!
+//! This channel works like [`mpsc`](crate::mpsc), except that the
+//! [`Receiver`] can be cloned as well. Every message is delivered to exactly
+//! one receiver, which makes the channel suitable as a work queue for a pool
+//! of tasks. Waiting receivers are woken in the order in which they started
+//! waiting, one per message, and so are senders waiting for capacity.
+//!
+//! A bounded channel created with [`channel`] holds at most `buffer`
+//! messages, regardless of the number of senders. An unbounded channel is
+//! created with [`unbounded`].
+//!
+//! # Disconnection
+//!
+//! When all [`Sender`] handles have been dropped, receivers yield the
+//! messages which are still queued and then terminate. When all [`Receiver`]
+//! handles have been dropped, queued messages are dropped and further
+//! attempts to send fail.
+//!
+//! # Clean Shutdown
+//!
+//! Like with [`mpsc`](crate::mpsc), calling [`close`](Receiver::close)
+//! prevents any further messages from being sent while allowing the
+//! receivers to drain the messages which are still queued.
+
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+use crate::mpsc::SendErrorKind;
+use crate::waiters::Waiters;
+
+/// Creates a bounded mpmc channel which holds at most `buffer` messages,
+/// returning the sender/receiver halves.
+///
+/// # Panics
+///
+/// This function panics if `buffer` is zero.
+pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "mpmc buffer must be greater than zero");
+    channel2(Some(buffer))
+}
+
+/// Creates an unbounded mpmc channel, returning the sender/receiver halves.
+///
+/// **Note** that the amount of available system memory is an implicit bound to
+/// the channel. Using an `unbounded` channel has the ability of causing the
+/// process to run out of memory. In this case, the process will be aborted.
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    channel2(None)
+}
+
+fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            buffer,
+            is_open: true,
+            num_senders: 1,
+            num_receivers: 1,
+            next_id: 2,
+            recv_waiters: Waiters::default(),
+            send_waiters: Waiters::default(),
+        }),
+    });
+
+    let tx = Sender { inner: inner.clone(), id: 0 };
+    let rx = Receiver { inner, id: 1, terminated: false };
+    (tx, rx)
+}
+
+/// The transmission end of an mpmc channel.
+///
+/// This value is created by the [`channel`] and [`unbounded`] functions.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+    id: usize,
+}
+
+/// The receiving end of an mpmc channel.
+///
+/// This value is created by the [`channel`] and [`unbounded`] functions.
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+    id: usize,
+    terminated: bool,
+}
+
+// We never project Pin<&mut Sender> or Pin<&mut Receiver> to `Pin<&mut T>`
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+
+    // Max number of queued messages. If `None` then the channel is unbounded.
+    buffer: Option<usize>,
+
+    // `false` once the channel has been closed or all receivers are gone.
+    is_open: bool,
+
+    num_senders: usize,
+    num_receivers: usize,
+
+    // Id of the next cloned handle, used to find its entry in the waiters.
+    next_id: usize,
+
+    // Receivers waiting for a message.
+    recv_waiters: Waiters,
+
+    // Senders waiting for capacity.
+    send_waiters: Waiters,
+}
+
+impl<T> Inner<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    fn is_full(&self) -> bool {
+        match self.buffer {
+            Some(buffer) => self.queue.len() >= buffer,
+            None => false,
+        }
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+        // Senders waiting for capacity will see that the channel is closed,
+        // and receivers may have to terminate.
+        self.send_waiters.wake_all();
+        self.recv_waiters.wake_all();
+    }
+}
+
+/*
+ *
+ * ===== impl Sender =====
+ *
+ */
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on this `Sender`, returning the message
+    /// if there was an error.
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.inner.lock();
+        let kind = if !state.is_open {
+            SendErrorKind::Disconnected
+        } else if state.is_full() {
+            SendErrorKind::Full
+        } else {
+            state.queue.push_back(msg);
+            state.recv_waiters.wake_one();
+            return Ok(());
+        };
+        Err(TrySendError { err: SendError { kind }, val: msg })
+    }
+
+    /// Send a message on the channel.
+    ///
+    /// This function should only be called after
+    /// [`poll_ready`](Sender::poll_ready) has reported that the channel is
+    /// ready to receive a message.
+    pub fn start_send(&self, msg: T) -> Result<(), SendError> {
+        self.try_send(msg).map_err(|e| e.err)
+    }
+
+    /// Polls the channel to determine if there is capacity to send a
+    /// message. Unlike with `mpsc`, the capacity isn't reserved for this
+    /// sender, so [`start_send`](Sender::start_send) may still fail with a
+    /// full channel if another sender took it first.
+    ///
+    /// Senders waiting for capacity are woken in the order in which they
+    /// started waiting. Only the task which polled this `Sender` last is
+    /// woken, so tasks sharing a sender should each use a clone of it.
+    pub fn poll_ready(&mut self, lw: &LocalWaker) -> Poll<Result<(), SendError>> {
+        let mut state = self.inner.lock();
+        if !state.is_open {
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if state.is_full() {
+            state.send_waiters.register(self.id, lw);
+            Poll::Pending
+        } else {
+            state.send_waiters.remove(self.id);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        !self.inner.lock().is_open
+    }
+
+    /// Closes this channel from the sender side, preventing any new messages.
+    pub fn close_channel(&self) {
+        self.inner.lock().close();
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let mut state = self.inner.lock();
+        state.num_senders += 1;
+        state.next_id += 1;
+        Sender { inner: self.inner.clone(), id: state.next_id }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        if !state.send_waiters.remove(self.id) && !state.is_full() {
+            // We may have been woken for capacity we're not going to use.
+            state.send_waiters.wake_one();
+        }
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            state.recv_waiters.wake_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/*
+ *
+ * ===== impl Receiver =====
+ *
+ */
+
+impl<T> Receiver<T> {
+    /// Closes the channel, without dropping this receiver.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling all receivers to drain messages that are queued.
+    pub fn close(&mut self) {
+        self.inner.lock().close();
+    }
+
+    /// Tries to receive the next message without notifying a context if empty.
+    ///
+    /// It is not recommended to call this function from inside of a future,
+    /// only when you've otherwise arranged to be notified when the channel is
+    /// no longer empty.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        let inner = self.inner.clone();
+        let mut state = inner.lock();
+        match self.next_message(&mut state) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    fn next_message(&mut self, state: &mut State<T>) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        match state.queue.pop_front() {
+            Some(msg) => {
+                state.recv_waiters.remove(self.id);
+                state.send_waiters.wake_one();
+                Poll::Ready(Some(msg))
+            }
+            None if state.num_senders == 0 || !state.is_open => {
+                self.terminated = true;
+                Poll::Ready(None)
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.inner.lock();
+        state.num_receivers += 1;
+        state.next_id += 1;
+        Receiver {
+            inner: self.inner.clone(),
+            id: state.next_id,
+            terminated: self.terminated,
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<T>> {
+        let inner = self.inner.clone();
+        let mut state = inner.lock();
+        let res = self.next_message(&mut state);
+        if res.is_pending() {
+            state.recv_waiters.register(self.id, lw);
+        }
+        res
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        if !state.recv_waiters.remove(self.id) && !state.queue.is_empty() {
+            // We may have been woken for a message we're not going to take.
+            state.recv_waiters.wake_one();
+        }
+        state.num_receivers -= 1;
+        if state.num_receivers == 0 {
+            state.close();
+            state.queue.clear();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver")
+            .field("id", &self.id)
+            .field("terminated", &self.terminated)
+            .finish()
+    }
+}
@@ -0,0 +1,626 @@
+use futures_core::future::{Future, FusedFuture};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::block::block_on_poll;
+use crate::waiters::Waiters;
+use super::{Recv, SendError, SendErrorKind, TryRecvError, TrySendError};
+
+/// Creates a bounded mpsc channel which holds at most `capacity` messages,
+/// however many senders there are.
+///
+/// Unlike [`channel`](super::channel), no slot is reserved per sender, so
+/// `capacity` is a hard bound on the number of queued messages. A slot can
+/// be reserved ahead of time with [`BoundedSender::reserve`].
+///
+/// With a `capacity` of zero the channel is a rendezvous channel:
+/// [`BoundedSender::send`] only completes once the receiver has taken the
+/// message, and [`BoundedSender::try_send`] only succeeds while the receiver
+/// is waiting for a message.
+pub fn bounded<T>(capacity: usize) -> (BoundedSender<T>, BoundedReceiver<T>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            capacity,
+            reserved: 0,
+            sent: 0,
+            received: 0,
+            is_open: true,
+            rx_alive: true,
+            rx_waiting: false,
+            num_senders: 1,
+            next_id: 1,
+            recv_task: None,
+            send_waiters: Waiters::default(),
+            handoff_task: None,
+        }),
+    });
+
+    let tx = BoundedSender { inner: inner.clone(), id: 0 };
+    let rx = BoundedReceiver { inner: Some(inner) };
+    (tx, rx)
+}
+
+/// The transmission end of a bounded mpsc channel with an exact capacity.
+///
+/// This value is created by the [`bounded`] function.
+pub struct BoundedSender<T> {
+    inner: Arc<Inner<T>>,
+    id: usize,
+}
+
+/// The receiving end of a bounded mpsc channel with an exact capacity.
+///
+/// This value is created by the [`bounded`] function.
+pub struct BoundedReceiver<T> {
+    inner: Option<Arc<Inner<T>>>,
+}
+
+// We never project Pin<&mut BoundedSender> or Pin<&mut BoundedReceiver> to
+// `Pin<&mut T>`
+impl<T> Unpin for BoundedSender<T> {}
+impl<T> Unpin for BoundedReceiver<T> {}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+
+    // Number of outstanding `Permit`s.
+    reserved: usize,
+
+    // Number of messages ever queued and taken by the receiver, used by
+    // rendezvous senders to find out when their message was taken.
+    sent: u64,
+    received: u64,
+
+    // `false` once the channel has been closed.
+    is_open: bool,
+    rx_alive: bool,
+
+    // `true` while the receiver is waiting for a message.
+    rx_waiting: bool,
+
+    num_senders: usize,
+
+    // Id of the next cloned handle or waiting future, used to find its entry
+    // in `send_waiters`.
+    next_id: usize,
+
+    // Handle to the receiver's task.
+    recv_task: Option<Waker>,
+
+    // Senders waiting for a free slot.
+    send_waiters: Waiters,
+
+    // The rendezvous sender waiting for its message to be taken. There can
+    // be only one, as only one message can be in the channel at a time.
+    handoff_task: Option<Waker>,
+}
+
+impl<T> Inner<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    fn has_capacity(&self) -> bool {
+        // A rendezvous channel holds the message being handed over.
+        self.queue.len() + self.reserved < self.capacity.max(1)
+    }
+
+    fn push(&mut self, msg: T) {
+        self.queue.push_back(msg);
+        self.sent += 1;
+        if let Some(task) = self.recv_task.take() {
+            task.wake();
+        }
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+        self.send_waiters.wake_all();
+    }
+
+    // Waits for a free slot under `key`, which is allocated on first use so
+    // that futures sharing a sender don't overwrite each other's wakers.
+    fn wait_for_slot(&mut self, key: &mut Option<usize>, lw: &LocalWaker) {
+        let key = match *key {
+            Some(key) => key,
+            None => {
+                self.next_id += 1;
+                *key = Some(self.next_id);
+                self.next_id
+            }
+        };
+        self.send_waiters.register(key, lw);
+    }
+
+    // Stops waiting for a slot, passing a wakeup we may have consumed on to
+    // the next waiting sender.
+    fn cancel_wait(&mut self, key: usize) {
+        if !self.send_waiters.remove(key) && self.has_capacity() {
+            self.send_waiters.wake_one();
+        }
+    }
+}
+
+/*
+ *
+ * ===== impl BoundedSender =====
+ *
+ */
+
+impl<T> BoundedSender<T> {
+    /// Attempts to send a message on this `BoundedSender`, returning the
+    /// message if there was an error.
+    ///
+    /// On a rendezvous channel this only succeeds while the receiver is
+    /// waiting for a message.
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.inner.lock();
+        let kind = if !state.is_open {
+            SendErrorKind::Disconnected
+        } else if !state.has_capacity() || (state.capacity == 0 && !state.rx_waiting) {
+            SendErrorKind::Full
+        } else {
+            state.push(msg);
+            return Ok(());
+        };
+        Err(TrySendError { err: SendError { kind }, val: msg })
+    }
+
+    /// Returns a future which sends `msg` once there is a free slot in the
+    /// channel.
+    ///
+    /// On a rendezvous channel the future only completes once the receiver
+    /// has taken the message.
+    pub fn send(&self, msg: T) -> BoundedSend<'_, T> {
+        BoundedSend { sender: self, key: None, msg: Some(msg), handoff: None }
+    }
+
+    /// Sends `msg`, blocking the current thread until there is a free slot
+    /// in the channel, or on a rendezvous channel until the receiver has
+    /// taken the message.
+    ///
+    /// See [`Sender::blocking_send`](super::Sender::blocking_send) for when
+    /// not to use this.
+    pub fn blocking_send(&self, msg: T) -> Result<(), SendError> {
+        let mut send = self.send(msg);
+        block_on_poll(|lw| Pin::new(&mut send).poll(lw))
+    }
+
+    /// Returns a future which reserves a slot in the channel, resolving to a
+    /// [`Permit`] which sends a message into that slot without waiting.
+    ///
+    /// Dropping the permit without sending releases the slot again.
+    pub fn reserve(&self) -> Reserve<'_, T> {
+        Reserve { sender: Some(self), key: None }
+    }
+
+    /// Polls the channel to determine if there is a free slot, reserving it
+    /// for this sender if there is.
+    ///
+    /// Only the task which polled this `BoundedSender` last is woken, so
+    /// tasks sharing a sender should use [`reserve`](BoundedSender::reserve)
+    /// or a clone of it instead.
+    pub fn poll_reserve(&mut self, lw: &LocalWaker) -> Poll<Result<Permit<'_, T>, SendError>> {
+        let mut key = Some(self.id);
+        self.poll_reserve_as(&mut key, lw)
+    }
+
+    fn poll_reserve_as(
+        &self,
+        key: &mut Option<usize>,
+        lw: &LocalWaker,
+    ) -> Poll<Result<Permit<'_, T>, SendError>> {
+        let mut state = self.inner.lock();
+        if !state.is_open {
+            if let Some(key) = *key {
+                state.send_waiters.remove(key);
+            }
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if state.has_capacity() {
+            if let Some(key) = *key {
+                state.send_waiters.remove(key);
+            }
+            state.reserved += 1;
+            Poll::Ready(Ok(Permit { sender: self }))
+        } else {
+            state.wait_for_slot(key, lw);
+            Poll::Pending
+        }
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        !self.inner.lock().is_open
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.inner.lock().queue.len()
+    }
+
+    /// Returns whether there are no messages in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity the channel was created with.
+    pub fn capacity(&self) -> usize {
+        self.inner.lock().capacity
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.inner.lock().num_senders
+    }
+
+    /// Returns whether this sender and `other` send into the same channel.
+    pub fn same_receiver(&self, other: &BoundedSender<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns whether this sender sends into the channel of `receiver`.
+    ///
+    /// This is `false` once `receiver` has terminated.
+    pub fn is_connected_to(&self, receiver: &BoundedReceiver<T>) -> bool {
+        match &receiver.inner {
+            Some(inner) => Arc::ptr_eq(&self.inner, inner),
+            None => false,
+        }
+    }
+
+    /// Closes this channel from the sender side, preventing any new messages.
+    pub fn close_channel(&self) {
+        self.inner.lock().close();
+    }
+}
+
+impl<T> Clone for BoundedSender<T> {
+    fn clone(&self) -> BoundedSender<T> {
+        let mut state = self.inner.lock();
+        state.num_senders += 1;
+        state.next_id += 1;
+        BoundedSender { inner: self.inner.clone(), id: state.next_id }
+    }
+}
+
+impl<T> Drop for BoundedSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        state.cancel_wait(self.id);
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            if let Some(task) = state.recv_task.take() {
+                task.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for BoundedSender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BoundedSender")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// A reserved slot in a bounded channel, created by
+/// [`BoundedSender::reserve`].
+pub struct Permit<'a, T> {
+    sender: &'a BoundedSender<T>,
+}
+
+impl<'a, T> Permit<'a, T> {
+    /// Sends `msg` into the reserved slot.
+    ///
+    /// This never waits, not even on a rendezvous channel. If the receiver
+    /// has been dropped in the meantime, the message is dropped.
+    pub fn send(self, msg: T) {
+        {
+            let mut state = self.sender.inner.lock();
+            state.reserved -= 1;
+            if state.rx_alive {
+                state.push(msg);
+            }
+        }
+        std::mem::forget(self);
+    }
+}
+
+impl<'a, T> Drop for Permit<'a, T> {
+    fn drop(&mut self) {
+        let mut state = self.sender.inner.lock();
+        state.reserved -= 1;
+        state.send_waiters.wake_one();
+        if let Some(task) = state.recv_task.take() {
+            task.wake();
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Permit<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Permit")
+            .finish()
+    }
+}
+
+/// Future for the [`reserve`](BoundedSender::reserve) method.
+#[must_use = "futures do nothing unless polled"]
+pub struct Reserve<'a, T> {
+    sender: Option<&'a BoundedSender<T>>,
+    // Our entry in the waiting senders, once we had to wait.
+    key: Option<usize>,
+}
+
+impl<'a, T> Future for Reserve<'a, T> {
+    type Output = Result<Permit<'a, T>, SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let sender = this.sender.expect("polled Reserve after completion");
+        let res = sender.poll_reserve_as(&mut this.key, lw);
+        if res.is_ready() {
+            this.sender = None;
+        }
+        res
+    }
+}
+
+impl<'a, T> FusedFuture for Reserve<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.sender.is_none()
+    }
+}
+
+impl<'a, T> Drop for Reserve<'a, T> {
+    fn drop(&mut self) {
+        if let (Some(sender), Some(key)) = (self.sender, self.key) {
+            sender.inner.lock().cancel_wait(key);
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for Reserve<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Reserve")
+            .finish()
+    }
+}
+
+/// Future for the [`send`](BoundedSender::send) method.
+#[must_use = "futures do nothing unless polled"]
+pub struct BoundedSend<'a, T> {
+    sender: &'a BoundedSender<T>,
+    // Our entry in the waiting senders, once we had to wait.
+    key: Option<usize>,
+    msg: Option<T>,
+    // The sequence number of our message on a rendezvous channel, once it
+    // has been queued.
+    handoff: Option<u64>,
+}
+
+impl<'a, T> Unpin for BoundedSend<'a, T> {}
+
+impl<'a, T> Future for BoundedSend<'a, T> {
+    type Output = Result<(), SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut state = this.sender.inner.lock();
+
+        if let Some(seq) = this.handoff {
+            return if state.received > seq {
+                this.handoff = None;
+                Poll::Ready(Ok(()))
+            } else if !state.rx_alive {
+                this.handoff = None;
+                Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }))
+            } else {
+                state.handoff_task = Some(lw.clone().into_waker());
+                Poll::Pending
+            };
+        }
+
+        if this.msg.is_none() {
+            panic!("polled BoundedSend after completion");
+        }
+        if !state.is_open {
+            if let Some(key) = this.key {
+                state.send_waiters.remove(key);
+            }
+            this.msg = None;
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if !state.has_capacity() {
+            state.wait_for_slot(&mut this.key, lw);
+            return Poll::Pending;
+        }
+
+        if let Some(key) = this.key {
+            state.send_waiters.remove(key);
+        }
+        state.push(this.msg.take().unwrap());
+        if state.capacity == 0 {
+            this.handoff = Some(state.sent - 1);
+            state.handoff_task = Some(lw.clone().into_waker());
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+impl<'a, T> FusedFuture for BoundedSend<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none() && self.handoff.is_none()
+    }
+}
+
+impl<'a, T> Drop for BoundedSend<'a, T> {
+    fn drop(&mut self) {
+        if let (Some(_), Some(key)) = (&self.msg, self.key) {
+            self.sender.inner.lock().cancel_wait(key);
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for BoundedSend<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BoundedSend")
+            .field("handoff", &self.handoff)
+            .finish()
+    }
+}
+
+/*
+ *
+ * ===== impl BoundedReceiver =====
+ *
+ */
+
+impl<T> BoundedReceiver<T> {
+    /// Closes the receiving half of the channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are buffered.
+    pub fn close(&mut self) {
+        if let Some(inner) = &self.inner {
+            inner.lock().close();
+        }
+    }
+
+    /// Tries to receive the next message without notifying a context if empty.
+    ///
+    /// It is not recommended to call this function from inside of a future,
+    /// only when you've otherwise arranged to be notified when the channel is
+    /// no longer empty.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.lock().queue.len())
+    }
+
+    /// Returns whether there are no messages in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity the channel was created with, or `0` once this
+    /// receiver has terminated.
+    pub fn capacity(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.lock().capacity)
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.lock().num_senders)
+    }
+
+    /// Returns a future which resolves to the next message, or to `None` once
+    /// the channel is closed and empty.
+    pub fn recv(&mut self) -> Recv<'_, Self> {
+        Recv { receiver: self }
+    }
+
+    /// Receives the next message, blocking the current thread until there is
+    /// one. Returns `None` once the channel is closed and empty.
+    ///
+    /// See [`Receiver::blocking_recv`](super::Receiver::blocking_recv) for
+    /// when not to use this.
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        block_on_poll(|lw| self.next_message(Some(lw)))
+    }
+
+    fn next_message(&mut self, lw: Option<&LocalWaker>) -> Poll<Option<T>> {
+        let inner = match &self.inner {
+            Some(inner) => inner.clone(),
+            None => return Poll::Ready(None),
+        };
+        let mut state = inner.lock();
+
+        match state.queue.pop_front() {
+            Some(msg) => {
+                state.received += 1;
+                state.rx_waiting = false;
+                state.send_waiters.wake_one();
+                if let Some(task) = state.handoff_task.take() {
+                    task.wake();
+                }
+                Poll::Ready(Some(msg))
+            }
+            // Outstanding permits may still send after the channel has been
+            // closed.
+            None if state.num_senders == 0 || (!state.is_open && state.reserved == 0) => {
+                drop(state);
+                self.inner = None;
+                Poll::Ready(None)
+            }
+            None => {
+                if let Some(lw) = lw {
+                    state.recv_task = Some(lw.clone().into_waker());
+                    state.rx_waiting = true;
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> FusedStream for BoundedReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<T> Stream for BoundedReceiver<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<T>> {
+        self.next_message(Some(lw))
+    }
+}
+
+impl<T> Drop for BoundedReceiver<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = &self.inner {
+            let mut state = inner.lock();
+            state.close();
+            state.rx_alive = false;
+            state.queue.clear();
+            if let Some(task) = state.handoff_task.take() {
+                task.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for BoundedReceiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BoundedReceiver")
+            .field("terminated", &self.inner.is_none())
+            .finish()
+    }
+}
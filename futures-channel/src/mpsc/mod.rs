@@ -11,6 +11,9 @@
 //! words, the channel provides backpressure.
 //!
 //! Unbounded channels are also available using the `unbounded` constructor.
+//! Channels with an exact capacity, which doesn't grow with the number of
+//! senders, and rendezvous channels are available using the `bounded`
+//! constructor when the `std` feature is enabled.
 //!
 //! # Disconnection
 //!
@@ -78,22 +81,34 @@
 // happens-before semantics required for the acquire / release semantics used
 // by the queue structure.
 
+use futures_core::future::{Future, FusedFuture};
 use futures_core::stream::{FusedStream, Stream};
 use futures_core::task::{LocalWaker, Waker, Poll};
 use futures_core::task::__internal::AtomicWaker;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering::SeqCst;
+use core::usize;
+#[cfg(feature = "std")]
 use std::any::Any;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
-use std::pin::Pin;
-use std::sync::{Arc, Mutex};
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::SeqCst;
-use std::usize;
 
+#[cfg(feature = "std")]
+use crate::block::block_on_poll;
+use crate::lock::Lock;
 use crate::mpsc::queue::Queue;
 
 mod queue;
 
+#[cfg(feature = "std")]
+mod bounded;
+#[cfg(feature = "std")]
+pub use self::bounded::{bounded, BoundedReceiver, BoundedSend, BoundedSender, Permit, Reserve};
+
 /// The transmission end of a bounded mpsc channel.
 ///
 /// This value is created by the [`channel`](channel) function.
@@ -105,7 +120,7 @@ pub struct Sender<T> {
     // Handle to the task that is blocked on this sender. This handle is sent
     // to the receiver half in order to be notified when the sender becomes
     // unblocked.
-    sender_task: Arc<Mutex<SenderTask>>,
+    sender_task: Arc<Lock<SenderTask>>,
 
     // True if the sender might be blocked. This is an optimization to avoid
     // having to lock the mutex most of the time.
@@ -144,25 +159,25 @@ impl<T> Unpin for UnboundedReceiver<T> {}
 /// The error type for [`Sender`s](Sender) used as `Sink`s.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SendError {
-    kind: SendErrorKind,
+    pub(crate) kind: SendErrorKind,
 }
 
 /// The error type returned from [`try_send`](Sender::try_send).
 #[derive(Clone, PartialEq, Eq)]
 pub struct TrySendError<T> {
-    err: SendError,
-    val: T,
+    pub(crate) err: SendError,
+    pub(crate) val: T,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum SendErrorKind {
+pub(crate) enum SendErrorKind {
     Full,
     Disconnected,
 }
 
 /// The error type returned from [`try_next`](Receiver::try_next).
 pub struct TryRecvError {
-    _inner: (),
+    pub(crate) _inner: (),
 }
 
 impl fmt::Display for SendError {
@@ -175,6 +190,7 @@ impl fmt::Display for SendError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for SendError {
     fn description(&self) -> &str {
         if self.is_full() {
@@ -221,6 +237,7 @@ impl<T> fmt::Display for TrySendError<T> {
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Any> Error for TrySendError<T> {
     fn description(&self) -> &str {
         if self.is_full() {
@@ -266,6 +283,7 @@ impl fmt::Display for TryRecvError {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for TryRecvError {
     fn description(&self) -> &str {
         "receiver channel is empty"
@@ -285,7 +303,7 @@ struct Inner<T> {
     message_queue: Queue<T>,
 
     // Atomic, FIFO queue used to send parked task handles to the receiver.
-    parked_queue: Queue<Arc<Mutex<SenderTask>>>,
+    parked_queue: Queue<Arc<Lock<SenderTask>>>,
 
     // Number of senders in existence
     num_senders: AtomicUsize,
@@ -333,12 +351,11 @@ impl SenderTask {
         }
     }
 
-    fn notify(&mut self) {
+    // Unparks the sender, returning its task. The task is to be woken only
+    // once the lock has been released, as the lock spins while it is held.
+    fn unpark(&mut self) -> Option<Waker> {
         self.is_parked = false;
-
-        if let Some(task) = self.task.take() {
-            task.wake();
-        }
+        self.task.take()
     }
 }
 
@@ -387,7 +404,7 @@ fn channel2<T>(buffer: Option<usize>) -> (Sender<T>, Receiver<T>) {
 
     let tx = Sender {
         inner: inner.clone(),
-        sender_task: Arc::new(Mutex::new(SenderTask::new())),
+        sender_task: Arc::new(Lock::new(SenderTask::new())),
         maybe_parked: false,
     };
 
@@ -432,6 +449,24 @@ impl<T> Sender<T> {
             .map_err(|e| e.err)
     }
 
+    /// Returns a future which sends `msg` once the channel is ready to
+    /// receive it.
+    pub fn send(&mut self, msg: T) -> SendMsg<'_, T> {
+        SendMsg { sender: self, msg: Some(msg) }
+    }
+
+    /// Sends `msg`, blocking the current thread until the channel is ready to
+    /// receive it.
+    ///
+    /// This is meant for threads which don't run an executor. Calling it
+    /// from within an asynchronous task blocks the executor's thread, which
+    /// may deadlock if the receiver runs on the same executor.
+    #[cfg(feature = "std")]
+    pub fn blocking_send(&mut self, msg: T) -> Result<(), SendError> {
+        block_on_poll(|lw| self.poll_ready(lw))?;
+        self.start_send(msg)
+    }
+
     // Do the send without failing.
     // Can be called only by bounded sender.
     fn do_send_b(&mut self, msg: T)
@@ -532,7 +567,7 @@ impl<T> Sender<T> {
 
     fn park(&mut self) {
         {
-            let mut sender = self.sender_task.lock().unwrap();
+            let mut sender = self.sender_task.lock();
             sender.task = None;
             sender.is_parked = true;
         }
@@ -578,6 +613,46 @@ impl<T> Sender<T> {
         !decode_state(self.inner.state.load(SeqCst)).is_open
     }
 
+    /// Returns the number of messages in the channel.
+    ///
+    /// This is only a snapshot, which may include messages that are still
+    /// in the process of being sent.
+    pub fn len(&self) -> usize {
+        self.inner.num_messages()
+    }
+
+    /// Returns whether there are no messages in the channel, see
+    /// [`len`](Sender::len).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can currently hold, which
+    /// is `buffer` plus one guaranteed slot per sender.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.inner.num_senders.load(SeqCst)
+    }
+
+    /// Returns whether this sender and `other` send into the same channel.
+    pub fn same_receiver(&self, other: &Sender<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns whether this sender sends into the channel of `receiver`.
+    ///
+    /// This is `false` once `receiver` has terminated.
+    pub fn is_connected_to(&self, receiver: &Receiver<T>) -> bool {
+        match &receiver.inner {
+            Some(inner) => Arc::ptr_eq(&self.inner, inner),
+            None => false,
+        }
+    }
+
     /// Closes this channel from the sender side, preventing any new messages.
     pub fn close_channel(&mut self) {
         // There's no need to park this sender, its dropping,
@@ -593,7 +668,7 @@ impl<T> Sender<T> {
         // lock in most cases
         if self.maybe_parked {
             // Get a lock on the task handle
-            let mut task = self.sender_task.lock().unwrap();
+            let mut task = self.sender_task.lock();
 
             if !task.is_parked {
                 self.maybe_parked = false;
@@ -629,6 +704,34 @@ impl<T> UnboundedSender<T> {
         self.0.is_closed()
     }
 
+    /// Returns the number of messages in the channel, see
+    /// [`Sender::len`].
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no messages in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.0.num_senders()
+    }
+
+    /// Returns whether this sender and `other` send into the same channel.
+    pub fn same_receiver(&self, other: &UnboundedSender<T>) -> bool {
+        self.0.same_receiver(&other.0)
+    }
+
+    /// Returns whether this sender sends into the channel of `receiver`.
+    ///
+    /// This is `false` once `receiver` has terminated.
+    pub fn is_connected_to(&self, receiver: &UnboundedReceiver<T>) -> bool {
+        self.0.is_connected_to(&receiver.0)
+    }
+
     /// Closes this channel from the sender side, preventing any new messages.
     pub fn close_channel(&self) {
         self.0.inner.set_closed();
@@ -703,7 +806,7 @@ impl<T> Clone for Sender<T> {
             if actual == curr {
                 return Sender {
                     inner: self.inner.clone(),
-                    sender_task: Arc::new(Mutex::new(SenderTask::new())),
+                    sender_task: Arc::new(Lock::new(SenderTask::new())),
                     maybe_parked: false,
                 };
             }
@@ -742,7 +845,10 @@ impl<T> Receiver<T> {
             // Wake up any threads waiting as they'll see that we've closed the
             // channel and will continue on their merry way.
             while let Some(task) = unsafe { inner.parked_queue.pop_spin() } {
-                task.lock().unwrap().notify();
+                let task = task.lock().unpark();
+                if let Some(task) = task {
+                    task.wake();
+                }
             }
         }
     }
@@ -764,6 +870,108 @@ impl<T> Receiver<T> {
         }
     }
 
+    /// Returns the number of messages in the channel.
+    ///
+    /// This is only a snapshot, which may include messages that are still
+    /// in the process of being sent.
+    pub fn len(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.num_messages())
+    }
+
+    /// Returns whether there are no messages in the channel, see
+    /// [`len`](Receiver::len).
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages the channel can currently hold, see
+    /// [`Sender::capacity`].
+    ///
+    /// This is `usize::MAX` for an unbounded channel, and `0` once the
+    /// receiver has terminated.
+    pub fn capacity(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.capacity())
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.num_senders.load(SeqCst))
+    }
+
+    /// Returns a future which resolves to the next message, or to `None` once
+    /// the channel is closed and empty.
+    pub fn recv(&mut self) -> Recv<'_, Self> {
+        Recv { receiver: self }
+    }
+
+    /// Receives the next message, blocking the current thread until there is
+    /// one. Returns `None` once the channel is closed and empty.
+    ///
+    /// This is meant for threads which don't run an executor. Calling it
+    /// from within an asynchronous task blocks the executor's thread, which
+    /// may deadlock if a sender runs on the same executor.
+    #[cfg(feature = "std")]
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        block_on_poll(|lw| self.recv().poll_recv(lw))
+    }
+
+    /// Moves up to `max` messages which are already queued into `buf`
+    /// without notifying a context, returning how many were moved.
+    ///
+    /// `Ok(0)` is returned once the channel is closed and empty, or if `max`
+    /// is zero. An error is returned if the channel is empty but still open.
+    pub fn try_recv_many(&mut self, buf: &mut Vec<T>, max: usize) -> Result<usize, TryRecvError> {
+        let mut count = 0;
+        while count < max && self.inner.is_some() {
+            match self.next_message() {
+                Poll::Ready(Some(msg)) => {
+                    buf.push(msg);
+                    count += 1;
+                }
+                Poll::Ready(None) => break,
+                Poll::Pending if count == 0 => {
+                    return Err(TryRecvError { _inner: () });
+                }
+                Poll::Pending => break,
+            }
+        }
+        Ok(count)
+    }
+
+    /// Waits for at least one message and then moves up to `max` messages
+    /// which are queued into `buf`, returning how many were moved.
+    ///
+    /// The current task is registered for a wakeup at most once per call,
+    /// rather than once per message. `Ready(0)` is returned once the channel
+    /// is closed and empty, or if `max` is zero.
+    pub fn poll_recv_many(
+        &mut self,
+        lw: &LocalWaker,
+        buf: &mut Vec<T>,
+        max: usize,
+    ) -> Poll<usize> {
+        if let Ok(count) = self.try_recv_many(buf, max) {
+            return Poll::Ready(count);
+        }
+
+        // There are no messages to read, in this case, park and check the
+        // queue again, just like `poll_next`.
+        self.inner.as_ref().unwrap().recv_task.register(lw);
+        match self.try_recv_many(buf, max) {
+            Ok(count) => Poll::Ready(count),
+            Err(_) => Poll::Pending,
+        }
+    }
+
+    /// Returns an iterator over the messages which are currently queued,
+    /// without waiting for more.
+    ///
+    /// This is mostly useful after [`close`](Receiver::close), to take the
+    /// remaining messages out of the channel in one go.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { receiver: self }
+    }
+
     fn next_message(&mut self) -> Poll<Option<T>> {
         let inner = self.inner.as_mut().expect("Receiver::next_message called after `None`");
         // Pop off a message
@@ -803,7 +1011,10 @@ impl<T> Receiver<T> {
     fn unpark_one(&mut self) {
         if let Some(inner) = &mut self.inner {
             if let Some(task) = unsafe { inner.parked_queue.pop_spin() } {
-                task.lock().unwrap().notify();
+                let task = task.lock().unpark();
+                if let Some(task) = task {
+                    task.wake();
+                }
             }
         }
     }
@@ -886,6 +1097,67 @@ impl<T> UnboundedReceiver<T> {
     pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
         self.0.try_next()
     }
+
+    /// Returns the number of messages in the channel, see
+    /// [`Receiver::len`].
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no messages in the channel.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of messages the channel can hold, which is
+    /// `usize::MAX` as the channel is unbounded, or `0` once this receiver
+    /// has terminated.
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of senders connected to the channel.
+    pub fn num_senders(&self) -> usize {
+        self.0.num_senders()
+    }
+
+    /// Returns a future which resolves to the next message, or to `None` once
+    /// the channel is closed and empty.
+    pub fn recv(&mut self) -> Recv<'_, Self> {
+        Recv { receiver: self }
+    }
+
+    /// Receives the next message, blocking the current thread until there is
+    /// one. Returns `None` once the channel is closed and empty.
+    ///
+    /// See [`Receiver::blocking_recv`] for when not to use this.
+    #[cfg(feature = "std")]
+    pub fn blocking_recv(&mut self) -> Option<T> {
+        self.0.blocking_recv()
+    }
+
+    /// Moves up to `max` messages which are already queued into `buf`,
+    /// see [`Receiver::try_recv_many`].
+    pub fn try_recv_many(&mut self, buf: &mut Vec<T>, max: usize) -> Result<usize, TryRecvError> {
+        self.0.try_recv_many(buf, max)
+    }
+
+    /// Waits for at least one message and then moves up to `max` messages
+    /// into `buf`, see [`Receiver::poll_recv_many`].
+    pub fn poll_recv_many(
+        &mut self,
+        lw: &LocalWaker,
+        buf: &mut Vec<T>,
+        max: usize,
+    ) -> Poll<usize> {
+        self.0.poll_recv_many(lw, buf, max)
+    }
+
+    /// Returns an iterator over the messages which are currently queued,
+    /// see [`Receiver::drain`].
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.0.drain()
+    }
 }
 
 impl<T> FusedStream for UnboundedReceiver<T> {
@@ -905,6 +1177,94 @@ impl<T> Stream for UnboundedReceiver<T> {
     }
 }
 
+/// Future for the [`send`](Sender::send) method.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct SendMsg<'a, T> {
+    sender: &'a mut Sender<T>,
+    msg: Option<T>,
+}
+
+// The message is never pinned
+impl<'a, T> Unpin for SendMsg<'a, T> {}
+
+impl<'a, T> FusedFuture for SendMsg<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none()
+    }
+}
+
+impl<'a, T> Future for SendMsg<'a, T> {
+    type Output = Result<(), SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if this.msg.is_none() {
+            panic!("polled SendMsg after completion");
+        }
+        match this.sender.poll_ready(lw) {
+            Poll::Ready(Ok(())) => {
+                let msg = this.msg.take().unwrap();
+                Poll::Ready(this.sender.start_send(msg))
+            }
+            Poll::Ready(Err(e)) => {
+                this.msg = None;
+                Poll::Ready(Err(e))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Future for the `recv` method of the mpsc receivers.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Recv<'a, R: ?Sized> {
+    receiver: &'a mut R,
+}
+
+impl<'a, R: ?Sized> Unpin for Recv<'a, R> {}
+
+impl<'a, R: FusedStream + Unpin + ?Sized> Recv<'a, R> {
+    fn poll_recv(&mut self, lw: &LocalWaker) -> Poll<Option<R::Item>> {
+        // Keep yielding `None` once the receiver is done instead of polling
+        // it again.
+        if self.receiver.is_terminated() {
+            Poll::Ready(None)
+        } else {
+            Pin::new(&mut *self.receiver).poll_next(lw)
+        }
+    }
+}
+
+impl<'a, R: FusedStream + Unpin + ?Sized> Future for Recv<'a, R> {
+    type Output = Option<R::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        self.poll_recv(lw)
+    }
+}
+
+/// Iterator returned by the `drain` method of the mpsc receivers.
+#[derive(Debug)]
+pub struct Drain<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<'a, T> Iterator for Drain<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.receiver.inner.is_none() {
+            return None;
+        }
+        match self.receiver.next_message() {
+            Poll::Ready(msg) => msg,
+            Poll::Pending => None,
+        }
+    }
+}
+
 /*
  *
  * ===== impl Inner =====
@@ -921,6 +1281,19 @@ impl<T> Inner<T> {
         }
     }
 
+    fn num_messages(&self) -> usize {
+        decode_state(self.state.load(SeqCst)).num_messages
+    }
+
+    // `buffer` plus one guaranteed slot per sender, or `usize::MAX` if the
+    // channel is unbounded.
+    fn capacity(&self) -> usize {
+        match self.buffer {
+            Some(buffer) => buffer + self.num_senders.load(SeqCst),
+            None => usize::MAX,
+        }
+    }
+
     // Clear `open` flag in the state, keep `num_messages` intact.
     fn set_closed(&self) {
         let curr = self.state.load(SeqCst);
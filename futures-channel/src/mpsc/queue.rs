@@ -42,12 +42,10 @@
 //       slightly modified
 
 pub use self::PopResult::*;
-use std::prelude::v1::*;
-
-use std::thread;
-use std::cell::UnsafeCell;
-use std::ptr;
-use std::sync::atomic::{AtomicPtr, Ordering};
+use alloc::boxed::Box;
+use core::cell::UnsafeCell;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
 
 /// A result of the `pop` function.
 pub enum PopResult<T> {
@@ -157,14 +155,25 @@ impl<T> Queue<T> {
                 //
                 // For now, thread::yield_now() is used, but it would
                 // probably be better to spin a few times then yield.
+                // Without `std`, we can only spin.
                 Inconsistent => {
-                    thread::yield_now();
+                    yield_now();
                 }
             }
         }
     }
 }
 
+#[cfg(feature = "std")]
+fn yield_now() {
+    std::thread::yield_now();
+}
+
+#[cfg(not(feature = "std"))]
+fn yield_now() {
+    core::sync::atomic::spin_loop_hint();
+}
+
 impl<T> Drop for Queue<T> {
     fn drop(&mut self) {
         unsafe {
@@ -1,14 +1,19 @@
 //! A channel for sending a single message between asynchronous tasks.
 
-use futures_core::future::Future;
+use futures_core::future::{Future, FusedFuture};
 use futures_core::task::{LocalWaker, Poll, Waker};
-use std::pin::Pin;
-use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering::SeqCst;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::AtomicBool;
+use core::sync::atomic::Ordering::SeqCst;
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt;
 
+#[cfg(feature = "std")]
+use crate::block::block_on_poll;
 use crate::lock::Lock;
 
 /// A future for a value that will be provided by another asynchronous task.
@@ -18,6 +23,7 @@ use crate::lock::Lock;
 #[derive(Debug)]
 pub struct Receiver<T> {
     inner: Arc<Inner<T>>,
+    terminated: bool,
 }
 
 /// A means of transmitting a single value to another task.
@@ -67,6 +73,13 @@ struct Inner<T> {
     /// Like `rx_task` above, except for the task blocked in
     /// `Sender::poll_cancel`. Additionally, `Lock` cannot be `UnsafeCell`.
     tx_task: Lock<Option<Waker>>,
+
+    /// The reason passed to `Receiver::close_with`, if any.
+    ///
+    /// This is filled in by the `Receiver` before it sets `complete`, and
+    /// only taken by the `Sender` after it has seen `complete`, so the two
+    /// halves never contend for the lock.
+    reason: Lock<Option<Box<dyn Any + Send>>>,
 }
 
 /// Creates a new one-shot channel for sending values across asynchronous tasks.
@@ -104,6 +117,7 @@ pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
     let inner = Arc::new(Inner::new());
     let receiver = Receiver {
         inner: inner.clone(),
+        terminated: false,
     };
     let sender = Sender {
         inner,
@@ -118,6 +132,7 @@ impl<T> Inner<T> {
             data: Lock::new(None),
             rx_task: Lock::new(None),
             tx_task: Lock::new(None),
+            reason: Lock::new(None),
         }
     }
 
@@ -234,6 +249,24 @@ impl<T> Inner<T> {
         }
     }
 
+    fn close_rx_with(&self, reason: Box<dyn Any + Send>) {
+        // Only the first close can leave a reason, as the sender may already
+        // be looking at it afterwards.
+        if !self.complete.load(SeqCst) {
+            if let Some(mut slot) = self.reason.try_lock() {
+                *slot = Some(reason);
+            }
+        }
+        self.close_rx();
+    }
+
+    fn take_reason(&self) -> Option<Box<dyn Any + Send>> {
+        if !self.complete.load(SeqCst) {
+            return None
+        }
+        self.reason.try_lock().and_then(|mut slot| slot.take())
+    }
+
     fn try_recv(&self) -> Result<Option<T>, Canceled> {
         // If we're complete, either `::close_rx` or `::drop_tx` was called.
         // We can assume a successful send if data is present.
@@ -361,6 +394,30 @@ impl<T> Sender<T> {
     pub fn is_canceled(&self) -> bool {
         self.inner.is_canceled()
     }
+
+    /// Returns a future which resolves once the associated
+    /// [`Receiver`](Receiver) has been dropped or closed, see
+    /// [`poll_cancel`](Sender::poll_cancel).
+    ///
+    /// The future resolves to the reason passed to
+    /// [`Receiver::close_with`], if there was one.
+    pub fn cancellation(&mut self) -> Cancellation<'_, T> {
+        Cancellation { sender: self }
+    }
+
+    /// Takes the reason the associated [`Receiver`](Receiver) passed to
+    /// [`close_with`](Receiver::close_with).
+    ///
+    /// Returns `None` if the `Receiver` is still open, if it was dropped or
+    /// closed without a reason, or if the reason has already been taken.
+    pub fn cancel_reason(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.inner.take_reason()
+    }
+
+    /// Tests to see whether this `Sender` sends to `receiver`.
+    pub fn is_connected_to(&self, receiver: &Receiver<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &receiver.inner)
+    }
 }
 
 impl<T> Drop for Sender<T> {
@@ -369,6 +426,24 @@ impl<T> Drop for Sender<T> {
     }
 }
 
+/// Future for the [`cancellation`](Sender::cancellation) method.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Cancellation<'a, T> {
+    sender: &'a mut Sender<T>,
+}
+
+impl<'a, T> Future for Cancellation<'a, T> {
+    type Output = Option<Box<dyn Any + Send>>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        match self.sender.poll_cancel(lw) {
+            Poll::Ready(()) => Poll::Ready(self.sender.cancel_reason()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 /// Error returned from a [`Receiver`](Receiver) when the corresponding
 /// [`Sender`](Sender) is dropped.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -380,6 +455,7 @@ impl fmt::Display for Canceled {
     }
 }
 
+#[cfg(feature = "std")]
 impl Error for Canceled {
     fn description(&self) -> &str {
         "oneshot canceled"
@@ -398,6 +474,16 @@ impl<T> Receiver<T> {
         self.inner.close_rx()
     }
 
+    /// Like [`close`](Receiver::close), but also leaves `reason` for the
+    /// [`Sender`](Sender), which can read it back with
+    /// [`cancel_reason`](Sender::cancel_reason) or
+    /// [`cancellation`](Sender::cancellation).
+    ///
+    /// If this receiver has already been closed, `reason` is dropped.
+    pub fn close_with<R: Any + Send>(&mut self, reason: R) {
+        self.inner.close_rx_with(Box::new(reason))
+    }
+
     /// Attempts to receive a message outside of the context of a task.
     ///
     /// Does not schedule a task wakeup or have any other side effects.
@@ -407,7 +493,29 @@ impl<T> Receiver<T> {
     ///
     /// Returns an error if the sender was dropped.
     pub fn try_recv(&mut self) -> Result<Option<T>, Canceled> {
-        self.inner.try_recv()
+        let res = self.inner.try_recv();
+        match res {
+            Ok(None) => {}
+            _ => self.terminated = true,
+        }
+        res
+    }
+
+    /// Waits for the message, blocking the current thread until it has been
+    /// sent or the [`Sender`](Sender) has been dropped.
+    ///
+    /// This is meant for threads which don't run an executor. Calling it
+    /// from within an asynchronous task blocks the executor's thread, which
+    /// may deadlock if the sender runs on the same executor.
+    #[cfg(feature = "std")]
+    pub fn blocking_recv(self) -> Result<T, Canceled> {
+        block_on_poll(|lw| self.inner.recv(lw))
+    }
+}
+
+impl<T> FusedFuture for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
     }
 }
 
@@ -415,10 +523,14 @@ impl<T> Future for Receiver<T> {
     type Output = Result<T, Canceled>;
 
     fn poll(
-        self: Pin<&mut Self>,
+        mut self: Pin<&mut Self>,
         lw: &LocalWaker,
     ) -> Poll<Result<T, Canceled>> {
-        self.inner.recv(lw)
+        let res = self.inner.recv(lw);
+        if res.is_ready() {
+            self.terminated = true;
+        }
+        res
     }
 }
 
@@ -0,0 +1,454 @@
+//! A multi-producer, single-consumer queue with multiple priority lanes.
+//!
+//! The channel is made up of a fixed number of lanes, numbered from `0` up
+//! to `lanes - 1`. [`Sender::send_with_priority`] queues a message on one of
+//! the lanes, and the [`Receiver`] stream always yields the oldest message of
+//! the highest-numbered lane which isn't empty. Within a lane, messages are
+//! received in the order in which they were sent.
+//!
+//! Every lane holds at most `buffer` messages. Once a lane is full, senders
+//! wait for the receiver to take a message out of that lane, so a flood of
+//! low-priority messages doesn't hold up senders of high-priority ones.
+//!
+//! Unlike with [`mpsc`](crate::mpsc), there is no extra slot per sender, so
+//! `buffer` is a hard bound. In turn, [`Sender::poll_ready`] doesn't reserve
+//! the free slot it reports: another sender may take it before the message
+//! is sent. [`Sender::send_with_priority`] doesn't have that problem, as it
+//! checks for a free slot and queues the message in one step.
+//!
+//! # Disconnection
+//!
+//! When all [`Sender`] handles have been dropped, the receiver yields the
+//! messages which are still queued and then terminates. When the
+//! [`Receiver`] has been dropped, further attempts to send fail.
+//!
+//! # Clean Shutdown
+//!
+//! Like with [`mpsc`](crate::mpsc), calling [`close`](Receiver::close)
+//! prevents any further messages from being sent while allowing the receiver
+//! to drain the messages which are still queued.
+
+use futures_core::future::{Future, FusedFuture};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use crate::mpsc::{SendError, TryRecvError, TrySendError};
+use crate::mpsc::SendErrorKind;
+use crate::waiters::Waiters;
+
+/// Creates a priority channel with `lanes` lanes which hold at most `buffer`
+/// messages each, returning the sender/receiver halves.
+///
+/// # Panics
+///
+/// This function panics if `lanes` or `buffer` is zero.
+pub fn channel<T>(lanes: usize, buffer: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(lanes > 0, "priority channel needs at least one lane");
+    assert!(buffer > 0, "priority buffer must be greater than zero");
+
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            lanes: (0..lanes).map(|_| Lane::default()).collect(),
+            buffer,
+            is_open: true,
+            num_senders: 1,
+            next_id: 1,
+            recv_task: None,
+        }),
+    });
+
+    let tx = Sender { inner: inner.clone(), id: 0 };
+    let rx = Receiver { inner: Some(inner) };
+    (tx, rx)
+}
+
+/// The transmission end of a priority channel.
+///
+/// This value is created by the [`channel`] function.
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+    id: usize,
+}
+
+/// The receiving end of a priority channel.
+///
+/// This value is created by the [`channel`] function.
+pub struct Receiver<T> {
+    inner: Option<Arc<Inner<T>>>,
+}
+
+// We never project Pin<&mut Sender> or Pin<&mut Receiver> to `Pin<&mut T>`
+impl<T> Unpin for Sender<T> {}
+impl<T> Unpin for Receiver<T> {}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    // Lanes by priority, the last one being the highest.
+    lanes: Vec<Lane<T>>,
+
+    // Max number of queued messages per lane.
+    buffer: usize,
+
+    // `false` once the channel has been closed.
+    is_open: bool,
+
+    num_senders: usize,
+
+    // Id of the next cloned sender or waiting future, used to find its entry
+    // in the waiters.
+    next_id: usize,
+
+    // Handle to the receiver's task.
+    recv_task: Option<Waker>,
+}
+
+struct Lane<T> {
+    queue: VecDeque<T>,
+
+    // Senders waiting for a free slot in this lane.
+    send_waiters: Waiters,
+}
+
+impl<T> Default for Lane<T> {
+    fn default() -> Lane<T> {
+        Lane { queue: VecDeque::new(), send_waiters: Waiters::default() }
+    }
+}
+
+impl<T> Inner<T> {
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl<T> State<T> {
+    fn lane(&mut self, level: usize) -> &mut Lane<T> {
+        let lanes = self.lanes.len();
+        assert!(level < lanes, "priority level {} out of range for {} lanes", level, lanes);
+        &mut self.lanes[level]
+    }
+
+    fn is_full(&mut self, level: usize) -> bool {
+        let buffer = self.buffer;
+        self.lane(level).queue.len() >= buffer
+    }
+
+    fn push(&mut self, level: usize, msg: T) {
+        self.lane(level).queue.push_back(msg);
+        if let Some(task) = self.recv_task.take() {
+            task.wake();
+        }
+    }
+
+    fn close(&mut self) {
+        self.is_open = false;
+        for lane in &mut self.lanes {
+            lane.send_waiters.wake_all();
+        }
+    }
+
+    // Waits for a free slot in lane `level` under `key`, which is allocated
+    // on first use so that futures sharing a sender don't overwrite each
+    // other's wakers.
+    fn wait_for_slot(&mut self, level: usize, key: &mut Option<usize>, lw: &LocalWaker) {
+        let key = match *key {
+            Some(key) => key,
+            None => {
+                self.next_id += 1;
+                *key = Some(self.next_id);
+                self.next_id
+            }
+        };
+        self.lane(level).send_waiters.register(key, lw);
+    }
+
+    // Stops waiting for a slot in lane `level`, passing a wakeup we may have
+    // consumed on to the next waiting sender.
+    fn cancel_wait(&mut self, level: usize, key: usize) {
+        if !self.lane(level).send_waiters.remove(key) && !self.is_full(level) {
+            self.lane(level).send_waiters.wake_one();
+        }
+    }
+}
+
+/*
+ *
+ * ===== impl Sender =====
+ *
+ */
+
+impl<T> Sender<T> {
+    /// Attempts to send a message on lane `level` without waiting, returning
+    /// the message if there was an error.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `level` isn't lower than the number of lanes.
+    pub fn try_send_with_priority(&self, level: usize, msg: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.inner.lock();
+        let kind = if !state.is_open {
+            SendErrorKind::Disconnected
+        } else if state.is_full(level) {
+            SendErrorKind::Full
+        } else {
+            state.push(level, msg);
+            return Ok(());
+        };
+        Err(TrySendError { err: SendError { kind }, val: msg })
+    }
+
+    /// Returns a future which sends `msg` on lane `level` once that lane
+    /// has a free slot.
+    ///
+    /// Senders waiting for a slot in the same lane are woken in the order in
+    /// which they started waiting.
+    ///
+    /// # Panics
+    ///
+    /// The future panics if `level` isn't lower than the number of lanes.
+    pub fn send_with_priority(&self, level: usize, msg: T) -> SendWithPriority<'_, T> {
+        SendWithPriority { sender: self, key: None, level, msg: Some(msg) }
+    }
+
+    /// Polls lane `level` to determine if there is a free slot. Unlike with
+    /// `mpsc`, the slot isn't reserved for this sender, so
+    /// [`try_send_with_priority`](Sender::try_send_with_priority) may still
+    /// fail with a full lane if another sender took it first.
+    ///
+    /// Only the task which polled this `Sender` last is woken, so tasks
+    /// sharing a sender should use
+    /// [`send_with_priority`](Sender::send_with_priority) or a clone of it
+    /// instead.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `level` isn't lower than the number of lanes.
+    pub fn poll_ready(&mut self, level: usize, lw: &LocalWaker) -> Poll<Result<(), SendError>> {
+        let mut state = self.inner.lock();
+        if !state.is_open {
+            state.lane(level).send_waiters.remove(self.id);
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if state.is_full(level) {
+            state.lane(level).send_waiters.register(self.id, lw);
+            Poll::Pending
+        } else {
+            state.lane(level).send_waiters.remove(self.id);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Returns the number of lanes of this channel.
+    pub fn lanes(&self) -> usize {
+        self.inner.lock().lanes.len()
+    }
+
+    /// Returns whether this channel is closed without needing a context.
+    pub fn is_closed(&self) -> bool {
+        !self.inner.lock().is_open
+    }
+
+    /// Closes this channel from the sender side, preventing any new messages.
+    pub fn close_channel(&self) {
+        self.inner.lock().close();
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let mut state = self.inner.lock();
+        state.num_senders += 1;
+        state.next_id += 1;
+        Sender { inner: self.inner.clone(), id: state.next_id }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.lock();
+        for level in 0..state.lanes.len() {
+            state.cancel_wait(level, self.id);
+        }
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            if let Some(task) = state.recv_task.take() {
+                task.wake();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+/// Future for the [`send_with_priority`](Sender::send_with_priority) method.
+#[must_use = "futures do nothing unless polled"]
+pub struct SendWithPriority<'a, T> {
+    sender: &'a Sender<T>,
+    // Our entry in the waiting senders, once we had to wait.
+    key: Option<usize>,
+    level: usize,
+    msg: Option<T>,
+}
+
+impl<'a, T> Unpin for SendWithPriority<'a, T> {}
+
+impl<'a, T> Future for SendWithPriority<'a, T> {
+    type Output = Result<(), SendError>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if this.msg.is_none() {
+            panic!("polled SendWithPriority after completion");
+        }
+
+        let mut state = this.sender.inner.lock();
+        if !state.is_open {
+            if let Some(key) = this.key {
+                state.lane(this.level).send_waiters.remove(key);
+            }
+            this.msg = None;
+            return Poll::Ready(Err(SendError { kind: SendErrorKind::Disconnected }));
+        }
+        if state.is_full(this.level) {
+            state.wait_for_slot(this.level, &mut this.key, lw);
+            return Poll::Pending;
+        }
+
+        if let Some(key) = this.key {
+            state.lane(this.level).send_waiters.remove(key);
+        }
+        state.push(this.level, this.msg.take().unwrap());
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<'a, T> FusedFuture for SendWithPriority<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.msg.is_none()
+    }
+}
+
+impl<'a, T> Drop for SendWithPriority<'a, T> {
+    fn drop(&mut self) {
+        if let (Some(_), Some(key)) = (&self.msg, self.key) {
+            self.sender.inner.lock().cancel_wait(self.level, key);
+        }
+    }
+}
+
+impl<'a, T> fmt::Debug for SendWithPriority<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SendWithPriority")
+            .field("level", &self.level)
+            .finish()
+    }
+}
+
+/*
+ *
+ * ===== impl Receiver =====
+ *
+ */
+
+impl<T> Receiver<T> {
+    /// Closes the receiving half of the channel, without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel while
+    /// still enabling the receiver to drain messages that are queued.
+    pub fn close(&mut self) {
+        if let Some(inner) = &self.inner {
+            inner.lock().close();
+        }
+    }
+
+    /// Tries to receive the next message without notifying a context if empty.
+    ///
+    /// It is not recommended to call this function from inside of a future,
+    /// only when you've otherwise arranged to be notified when the channel is
+    /// no longer empty.
+    pub fn try_next(&mut self) -> Result<Option<T>, TryRecvError> {
+        match self.next_message(None) {
+            Poll::Ready(msg) => Ok(msg),
+            Poll::Pending => Err(TryRecvError { _inner: () }),
+        }
+    }
+
+    fn next_message(&mut self, lw: Option<&LocalWaker>) -> Poll<Option<T>> {
+        let inner = match &self.inner {
+            Some(inner) => inner.clone(),
+            None => return Poll::Ready(None),
+        };
+        let mut state = inner.lock();
+
+        let msg = state.lanes.iter_mut().rev().find_map(|lane| {
+            let msg = lane.queue.pop_front()?;
+            lane.send_waiters.wake_one();
+            Some(msg)
+        });
+
+        match msg {
+            Some(msg) => Poll::Ready(Some(msg)),
+            None if state.num_senders == 0 || !state.is_open => {
+                drop(state);
+                self.inner = None;
+                Poll::Ready(None)
+            }
+            None => {
+                if let Some(lw) = lw {
+                    state.recv_task = Some(lw.clone().into_waker());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<T>> {
+        self.next_message(Some(lw))
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = &self.inner {
+            let mut state = inner.lock();
+            state.close();
+            for lane in &mut state.lanes {
+                lane.queue.clear();
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver")
+            .field("terminated", &self.inner.is_none())
+            .finish()
+    }
+}
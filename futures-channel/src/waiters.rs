@@ -0,0 +1,49 @@
+//! A queue of waiting tasks shared by the channels.
+
+use futures_core::task::{LocalWaker, Waker};
+use std::collections::VecDeque;
+
+/// A FIFO queue of tasks waiting on a channel, each identified by the id of
+/// the handle it is waiting on.
+#[derive(Default)]
+pub(crate) struct Waiters {
+    queue: VecDeque<(usize, Waker)>,
+}
+
+impl Waiters {
+    /// Registers the task of handle `id`, keeping its position if it is
+    /// already waiting.
+    pub(crate) fn register(&mut self, id: usize, lw: &LocalWaker) {
+        match self.queue.iter_mut().find(|(i, _)| *i == id) {
+            Some((_, waker)) => {
+                if !lw.will_wake_nonlocal(waker) {
+                    *waker = lw.clone().into_waker();
+                }
+            }
+            None => self.queue.push_back((id, lw.clone().into_waker())),
+        }
+    }
+
+    /// Returns `true` if handle `id` was waiting.
+    pub(crate) fn remove(&mut self, id: usize) -> bool {
+        match self.queue.iter().position(|(i, _)| *i == id) {
+            Some(index) => {
+                self.queue.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn wake_one(&mut self) {
+        if let Some((_, waker)) = self.queue.pop_front() {
+            waker.wake();
+        }
+    }
+
+    pub(crate) fn wake_all(&mut self) {
+        for (_, waker) in self.queue.drain(..) {
+            waker.wake();
+        }
+    }
+}
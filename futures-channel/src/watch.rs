@@ -0,0 +1,356 @@
+//! A single-value channel where receivers only see the most recent value.
+//!
+//! Channel creation provides a [`Sender`] and a [`Receiver`] handle, and the
+//! channel starts out holding an initial value. [`Sender::send`] overwrites
+//! the current value, and every receiver can look at it with
+//! [`Receiver::borrow`]. Receivers don't queue values: a receiver which is
+//! notified of a change only ever sees the latest value, however many values
+//! were sent in between.
+//!
+//! Receivers are notified of changes through the [`changed`](Receiver::changed)
+//! future or by using the [`Receiver`] as a [`Stream`], which yields a clone
+//! of the current value every time it changed since the last time the
+//! receiver looked.
+//!
+//! [`Stream`]: ../../futures_core/stream/trait.Stream.html
+//!
+//! # Disconnection
+//!
+//! Once the [`Sender`] has been dropped, waiting for a change fails and the
+//! receiver streams terminate. The last value can still be borrowed.
+
+use futures_core::future::{Future, FusedFuture};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use futures_core::task::__internal::AtomicWaker;
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::SeqCst;
+
+/// Creates a new watch channel holding `init`, returning the sender/receiver
+/// halves.
+///
+/// Further receivers can be created with [`Sender::subscribe`] or by cloning
+/// the [`Receiver`].
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        state: AtomicUsize::new(0),
+        wakers: Mutex::new(Vec::new()),
+    });
+
+    let rx = Receiver::new(shared.clone(), 0);
+    (Sender { shared }, rx)
+}
+
+/// The sending half of a watch channel.
+///
+/// This value is created by the [`channel`](channel) function.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The receiving half of a watch channel.
+///
+/// This value is created by the [`channel`](channel) function or by
+/// [`Sender::subscribe`].
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    // The version of the value this receiver has last seen.
+    version: usize,
+    waker: Arc<AtomicWaker>,
+    terminated: bool,
+}
+
+// The channels do not ever project Pin to the inner T
+impl<T> Unpin for Receiver<T> {}
+impl<T> Unpin for Sender<T> {}
+
+struct Shared<T> {
+    value: RwLock<T>,
+
+    // The version of `value` shifted left by one, with the lowest bit set
+    // once the sender has been dropped.
+    state: AtomicUsize,
+
+    // Wakers of all receivers. Each receiver registers its task with its own
+    // `AtomicWaker`, so sending a value doesn't allocate.
+    wakers: Mutex<Vec<Arc<AtomicWaker>>>,
+}
+
+const CLOSED: usize = 1;
+const VERSION_STEP: usize = 2;
+
+/// A reference to the current value of a watch channel.
+///
+/// This is returned by [`Receiver::borrow`]. The value can't be changed
+/// while a `Ref` to it is alive, so it should not be held for long.
+pub struct Ref<'a, T> {
+    guard: RwLockReadGuard<'a, T>,
+}
+
+/// The error type returned from [`Sender::send`] when there are no
+/// receivers left.
+///
+/// The value which couldn't be sent is returned in the error.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// Error returned by the [`Changed`] future when the [`Sender`] has been
+/// dropped.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RecvError {
+    _inner: (),
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("SendError")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "send failed because there are no receivers")
+    }
+}
+
+impl<T: Any> Error for SendError<T> {
+    fn description(&self) -> &str {
+        "send failed because there are no receivers"
+    }
+}
+
+impl<T> SendError<T> {
+    /// Returns the value that was attempted to be sent but failed.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "sender was dropped")
+    }
+}
+
+impl Error for RecvError {
+    fn description(&self) -> &str {
+        "sender was dropped"
+    }
+}
+
+impl<'a, T> Deref for Ref<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Ref<'a, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, fmt)
+    }
+}
+
+impl<T> Shared<T> {
+    fn borrow(&self) -> Ref<'_, T> {
+        Ref { guard: self.value.read().unwrap() }
+    }
+
+    fn wake_all(&self) {
+        for waker in self.wakers.lock().unwrap().iter() {
+            waker.wake();
+        }
+    }
+}
+
+/*
+ *
+ * ===== impl Sender =====
+ *
+ */
+
+impl<T> Sender<T> {
+    /// Replaces the current value and notifies all receivers.
+    ///
+    /// An error is returned if there are no receivers, in which case the
+    /// value is handed back and the current value is left unchanged.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.is_closed() {
+            return Err(SendError(value));
+        }
+
+        *self.shared.value.write().unwrap() = value;
+        self.shared.state.fetch_add(VERSION_STEP, SeqCst);
+        self.shared.wake_all();
+        Ok(())
+    }
+
+    /// Returns a reference to the current value.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.borrow()
+    }
+
+    /// Returns `true` if all receivers have been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.shared.wakers.lock().unwrap().is_empty()
+    }
+
+    /// Creates a new receiver which is notified of every change from now
+    /// on.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let version = self.shared.state.load(SeqCst) & !CLOSED;
+        Receiver::new(self.shared.clone(), version)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.state.fetch_or(CLOSED, SeqCst);
+        self.shared.wake_all();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender")
+            .finish()
+    }
+}
+
+/*
+ *
+ * ===== impl Receiver =====
+ *
+ */
+
+impl<T> Receiver<T> {
+    fn new(shared: Arc<Shared<T>>, version: usize) -> Receiver<T> {
+        let waker = Arc::new(AtomicWaker::new());
+        shared.wakers.lock().unwrap().push(waker.clone());
+        Receiver { shared, version, waker, terminated: false }
+    }
+
+    /// Returns a reference to the current value.
+    ///
+    /// This doesn't mark the value as seen, so a pending change is still
+    /// reported by [`changed`](Receiver::changed) afterwards.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        self.shared.borrow()
+    }
+
+    /// Returns a future which completes once the value has changed since
+    /// this receiver last saw it, marking the new value as seen.
+    ///
+    /// The future resolves to an error once the [`Sender`] has been dropped
+    /// and there is no change left to observe.
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: Some(self) }
+    }
+
+    /// Polls for a change of the value, see [`changed`](Receiver::changed).
+    pub fn poll_changed(&mut self, lw: &LocalWaker) -> Poll<Result<(), RecvError>> {
+        self.waker.register(lw);
+
+        let state = self.shared.state.load(SeqCst);
+        let version = state & !CLOSED;
+        if version != self.version {
+            self.version = version;
+            Poll::Ready(Ok(()))
+        } else if state & CLOSED == CLOSED {
+            Poll::Ready(Err(RecvError { _inner: () }))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    /// Creates a new receiver which has seen the same version of the value
+    /// as this one.
+    fn clone(&self) -> Receiver<T> {
+        let mut rx = Receiver::new(self.shared.clone(), self.version);
+        rx.terminated = self.terminated;
+        rx
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut wakers = self.shared.wakers.lock().unwrap();
+        if let Some(index) = wakers.iter().position(|w| Arc::ptr_eq(w, &self.waker)) {
+            wakers.swap_remove(index);
+        }
+    }
+}
+
+impl<T> FusedStream for Receiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        match self.poll_changed(lw) {
+            Poll::Ready(Ok(())) => Poll::Ready(Some(self.borrow().clone())),
+            Poll::Ready(Err(_)) => {
+                self.terminated = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver")
+            .field("version", &(self.version / VERSION_STEP))
+            .finish()
+    }
+}
+
+/// Future for the [`changed`](Receiver::changed) method.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Changed<'a, T> {
+    receiver: Option<&'a mut Receiver<T>>,
+}
+
+impl<'a, T> FusedFuture for Changed<'a, T> {
+    fn is_terminated(&self) -> bool {
+        self.receiver.is_none()
+    }
+}
+
+impl<'a, T> Future for Changed<'a, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let res = self.receiver.as_mut()
+            .expect("polled Changed after completion")
+            .poll_changed(lw);
+        if res.is_ready() {
+            self.receiver = None;
+        }
+        res
+    }
+}
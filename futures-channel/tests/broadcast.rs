@@ -0,0 +1,141 @@
+#![feature(futures_api)]
+
+use futures::channel::broadcast::{self, Lagged, TryRecvError};
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::thread;
+
+trait AssertSend: Send {}
+impl AssertSend for broadcast::Sender<i32> {}
+impl AssertSend for broadcast::Receiver<i32> {}
+
+#[test]
+fn every_receiver_sees_every_message() {
+    let (tx, rx1) = broadcast::channel::<i32>(16);
+    let rx2 = tx.subscribe();
+    let rx3 = rx1.clone();
+
+    for i in 0..3 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    for rx in vec![rx1, rx2, rx3] {
+        let v: Vec<_> = block_on(rx.collect());
+        assert_eq!(v, vec![Ok(0), Ok(1), Ok(2)]);
+    }
+}
+
+#[test]
+fn subscribe_skips_buffered_messages() {
+    let (tx, mut rx1) = broadcast::channel::<i32>(16);
+    tx.send(1).unwrap();
+
+    let mut rx2 = tx.subscribe();
+    tx.send(2).unwrap();
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    assert_eq!(rx1.try_recv(), Ok(2));
+    assert_eq!(rx2.try_recv(), Ok(2));
+    assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+
+    drop(tx);
+    assert_eq!(rx2.try_recv(), Err(TryRecvError::Closed));
+}
+
+#[test]
+fn slow_receiver_lags() {
+    let (tx, mut rx) = broadcast::channel::<i32>(2);
+    for i in 0..5 {
+        tx.send(i).unwrap();
+    }
+
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Lagged(3)));
+    assert_eq!(rx.try_recv(), Ok(3));
+
+    tx.send(5).unwrap();
+    tx.send(6).unwrap();
+    drop(tx);
+
+    let v: Vec<_> = block_on(rx.collect());
+    assert_eq!(v, vec![Err(Lagged(1)), Ok(5), Ok(6)]);
+}
+
+#[test]
+fn send_without_receivers_fails() {
+    let (tx, rx) = broadcast::channel::<i32>(1);
+    drop(rx);
+    assert_eq!(tx.send(1).unwrap_err().into_inner(), 1);
+
+    let mut rx = tx.subscribe();
+    tx.send(2).unwrap();
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn receiver_is_woken_and_fused() {
+    let (tx, mut rx) = broadcast::channel::<i32>(4);
+    let (lw, counter) = new_count_waker();
+
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Pending);
+    tx.send(1).unwrap();
+    assert_eq!(counter, 1);
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Ready(Some(Ok(1))));
+
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Pending);
+    drop(tx);
+    assert_eq!(counter, 2);
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Ready(None));
+    assert!(futures::stream::FusedStream::is_terminated(&rx));
+    assert_eq!(rx.poll_next_unpin(noop_local_waker_ref()), Poll::Ready(None));
+}
+
+#[test]
+fn sender_as_sink() {
+    let (mut tx, rx) = broadcast::channel::<i32>(8);
+    let rx2 = rx.clone();
+
+    block_on(tx.send_all(&mut stream::iter(vec![1, 2, 3]))).unwrap();
+    drop(tx);
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![Ok(1), Ok(2), Ok(3)]);
+    assert_eq!(block_on(rx2.collect::<Vec<_>>()), vec![Ok(1), Ok(2), Ok(3)]);
+}
+
+#[test]
+fn closing_sink_ends_receivers() {
+    let (mut tx, rx) = broadcast::channel::<i32>(8);
+    let tx2 = tx.clone();
+
+    block_on(tx.send_all(&mut stream::iter(vec![1, 2]))).unwrap();
+    block_on(tx.close()).unwrap();
+    assert!(tx2.is_closed());
+    assert_eq!(tx2.send(3).unwrap_err().into_inner(), 3);
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![Ok(1), Ok(2)]);
+}
+
+#[test]
+fn broadcast_across_threads() {
+    let (tx, rx) = broadcast::channel::<i32>(100);
+    let receivers: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || block_on(rx.collect::<Vec<_>>()))
+        })
+        .collect();
+    drop(rx);
+
+    for i in 0..100 {
+        tx.send(i).unwrap();
+    }
+    drop(tx);
+
+    let expected: Vec<_> = (0..100).map(Ok).collect();
+    for handle in receivers {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
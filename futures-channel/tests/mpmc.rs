@@ -0,0 +1,163 @@
+#![feature(futures_api)]
+
+use futures::channel::mpmc;
+use futures::executor::block_on;
+use futures::sink::SinkExt;
+use futures::stream::{self, FusedStream, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::collections::HashSet;
+use std::thread;
+
+trait AssertSend: Send + Sync {}
+impl AssertSend for mpmc::Sender<i32> {}
+impl AssertSend for mpmc::Receiver<i32> {}
+
+#[test]
+fn each_message_is_received_once() {
+    let (tx, rx1) = mpmc::unbounded::<i32>();
+    let mut rx2 = rx1.clone();
+
+    for i in 0..4 {
+        tx.try_send(i).unwrap();
+    }
+    drop(tx);
+
+    let mut rx1 = rx1;
+    assert_eq!(rx1.try_next().unwrap(), Some(0));
+    assert_eq!(rx2.try_next().unwrap(), Some(1));
+    assert_eq!(rx1.try_next().unwrap(), Some(2));
+    assert_eq!(rx2.try_next().unwrap(), Some(3));
+    assert_eq!(rx1.try_next().unwrap(), None);
+    assert_eq!(rx2.try_next().unwrap(), None);
+    assert!(rx1.is_terminated());
+}
+
+#[test]
+fn bounded_channel_has_exact_capacity() {
+    let (mut tx, mut rx) = mpmc::channel::<i32>(2);
+    let tx2 = tx.clone();
+
+    tx.try_send(1).unwrap();
+    tx2.try_send(2).unwrap();
+    assert!(tx.try_send(3).unwrap_err().is_full());
+
+    let (lw, counter) = new_count_waker();
+    assert_eq!(tx.poll_ready(&lw), Poll::Pending);
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter, 1);
+    assert_eq!(tx.poll_ready(&lw), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn every_waiting_sender_is_woken() {
+    let (mut tx1, mut rx) = mpmc::channel::<i32>(1);
+    let mut tx2 = tx1.clone();
+    tx1.try_send(1).unwrap();
+
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+    assert_eq!(tx1.poll_ready(&lw1), Poll::Pending);
+    assert_eq!(tx2.poll_ready(&lw2), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 1);
+    assert_eq!(counter2, 0);
+    assert_eq!(tx1.poll_ready(&lw1), Poll::Ready(Ok(())));
+    tx1.try_send(2).unwrap();
+
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert_eq!(counter2, 1);
+    assert_eq!(tx2.poll_ready(&lw2), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn receivers_are_woken_in_order() {
+    let (tx, mut rx1) = mpmc::unbounded::<i32>();
+    let mut rx2 = rx1.clone();
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    assert_eq!(rx1.poll_next_unpin(&lw1), Poll::Pending);
+    assert_eq!(rx2.poll_next_unpin(&lw2), Poll::Pending);
+
+    tx.try_send(1).unwrap();
+    assert_eq!(counter1, 1);
+    assert_eq!(counter2, 0);
+
+    tx.try_send(2).unwrap();
+    assert_eq!(counter2, 1);
+
+    assert_eq!(rx2.poll_next_unpin(&lw2), Poll::Ready(Some(1)));
+    assert_eq!(rx1.poll_next_unpin(&lw1), Poll::Ready(Some(2)));
+}
+
+#[test]
+fn dropped_receiver_passes_on_wakeup() {
+    let (tx, rx1) = mpmc::unbounded::<i32>();
+    let mut rx2 = rx1.clone();
+    let mut rx1 = rx1;
+    let (lw, counter) = new_count_waker();
+
+    assert_eq!(rx1.poll_next_unpin(noop_local_waker_ref()), Poll::Pending);
+    assert_eq!(rx2.poll_next_unpin(&lw), Poll::Pending);
+
+    tx.try_send(1).unwrap();
+    assert_eq!(counter, 0);
+    drop(rx1);
+    assert_eq!(counter, 1);
+    assert_eq!(rx2.poll_next_unpin(&lw), Poll::Ready(Some(1)));
+}
+
+#[test]
+fn close_and_disconnect() {
+    let (tx, mut rx) = mpmc::channel::<i32>(4);
+    let rx2 = rx.clone();
+
+    tx.try_send(1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert!(tx.try_send(2).unwrap_err().is_disconnected());
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1]);
+    assert_eq!(block_on(rx2.collect::<Vec<_>>()), vec![]);
+
+    let (tx, rx) = mpmc::channel::<i32>(4);
+    drop(rx);
+    assert!(tx.try_send(1).unwrap_err().is_disconnected());
+}
+
+#[test]
+fn sender_as_sink() {
+    let (mut tx, rx) = mpmc::channel::<i32>(1);
+    let handle = thread::spawn(move || block_on(rx.collect::<Vec<_>>()));
+
+    block_on(tx.send_all(&mut stream::iter(0..10))).unwrap();
+    drop(tx);
+    assert_eq!(handle.join().unwrap(), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn work_is_distributed_across_threads() {
+    const N: i32 = 1000;
+    let (tx, rx) = mpmc::channel::<i32>(8);
+
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let rx = rx.clone();
+            thread::spawn(move || block_on(rx.collect::<Vec<_>>()))
+        })
+        .collect();
+    drop(rx);
+
+    let mut tx = tx;
+    block_on(tx.send_all(&mut stream::iter(0..N))).unwrap();
+    drop(tx);
+
+    let mut seen = HashSet::new();
+    for worker in workers {
+        for i in worker.join().unwrap() {
+            assert!(seen.insert(i));
+        }
+    }
+    assert_eq!(seen.len(), N as usize);
+}
@@ -0,0 +1,191 @@
+#![feature(futures_api)]
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::thread;
+
+trait AssertSend: Send + Sync {}
+impl AssertSend for mpsc::BoundedSender<i32> {}
+impl AssertSend for mpsc::BoundedReceiver<i32> {}
+
+#[test]
+fn capacity_does_not_grow_with_senders() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(2);
+    let senders: Vec<_> = (0..10).map(|_| tx.clone()).collect();
+    assert_eq!(tx.capacity(), 2);
+    assert_eq!(rx.capacity(), 2);
+
+    senders[0].try_send(1).unwrap();
+    senders[1].try_send(2).unwrap();
+    for tx in &senders {
+        assert!(tx.try_send(3).unwrap_err().is_full());
+    }
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    senders[2].try_send(3).unwrap();
+}
+
+#[test]
+fn send_waits_for_capacity() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(1);
+    let (lw, counter) = new_count_waker();
+
+    tx.try_send(1).unwrap();
+    let mut send = tx.send(2);
+    assert_eq!(send.poll_unpin(&lw), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter, 1);
+    assert_eq!(send.poll_unpin(&lw), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+}
+
+#[test]
+fn reserve_guarantees_a_slot() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(1);
+    let lw = noop_local_waker_ref();
+
+    let permit = match tx.reserve().poll_unpin(lw) {
+        Poll::Ready(Ok(permit)) => permit,
+        _ => panic!("reserve should complete"),
+    };
+    assert!(tx.try_send(1).unwrap_err().is_full());
+    assert!(tx.reserve().poll_unpin(lw).is_pending());
+
+    permit.send(2);
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+
+    let permit = block_on(tx.reserve()).unwrap();
+    drop(permit);
+    tx.try_send(3).unwrap();
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+}
+
+#[test]
+fn futures_sharing_a_sender_are_all_woken() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(1);
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    tx.try_send(1).unwrap();
+    let mut send1 = tx.send(2);
+    let mut send2 = tx.send(3);
+    assert_eq!(send1.poll_unpin(&lw1), Poll::Pending);
+    assert_eq!(send2.poll_unpin(&lw2), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 1);
+    assert_eq!(counter2, 0);
+    assert_eq!(send1.poll_unpin(&lw1), Poll::Ready(Ok(())));
+
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert_eq!(counter2, 1);
+    assert_eq!(send2.poll_unpin(&lw2), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+}
+
+#[test]
+fn dropped_sender_passes_wakeup_on() {
+    let (mut tx1, mut rx) = mpsc::bounded::<i32>(1);
+    let tx2 = tx1.clone();
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    tx1.try_send(1).unwrap();
+    assert!(tx1.poll_reserve(&lw1).is_pending());
+    let mut send = tx2.send(2);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Pending);
+
+    // `tx1` is woken for the free slot but dropped without taking it.
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 1);
+    drop(tx1);
+    assert_eq!(counter2, 1);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn dropped_waiting_sender_is_unregistered() {
+    let (mut tx1, mut rx) = mpsc::bounded::<i32>(1);
+    let tx2 = tx1.clone();
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    tx1.try_send(1).unwrap();
+    assert!(tx1.poll_reserve(&lw1).is_pending());
+    let mut send = tx2.send(2);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Pending);
+
+    drop(tx1);
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 0);
+    assert_eq!(counter2, 1);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn rendezvous_send_completes_when_taken() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(0);
+    let lw = noop_local_waker_ref();
+
+    assert!(tx.try_send(1).unwrap_err().is_full());
+
+    let mut send = tx.send(1);
+    assert_eq!(send.poll_unpin(lw), Poll::Pending);
+    assert_eq!(send.poll_unpin(lw), Poll::Pending);
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Ready(Some(1)));
+    assert_eq!(send.poll_unpin(lw), Poll::Ready(Ok(())));
+
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Pending);
+    tx.try_send(2).unwrap();
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Ready(Some(2)));
+}
+
+#[test]
+fn rendezvous_across_threads() {
+    let (tx, rx) = mpsc::bounded::<i32>(0);
+    let handle = thread::spawn(move || {
+        for i in 0..10 {
+            block_on(tx.send(i)).unwrap();
+        }
+    });
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), (0..10).collect::<Vec<_>>());
+    handle.join().unwrap();
+}
+
+#[test]
+fn blocking_rendezvous_across_threads() {
+    let (tx, mut rx) = mpsc::bounded::<i32>(0);
+    let handle = thread::spawn(move || {
+        for i in 0..10 {
+            tx.blocking_send(i).unwrap();
+        }
+    });
+
+    for i in 0..10 {
+        assert_eq!(rx.blocking_recv(), Some(i));
+    }
+    assert_eq!(block_on(rx.recv()), None);
+    handle.join().unwrap();
+}
+
+#[test]
+fn disconnect() {
+    let (tx, rx) = mpsc::bounded::<i32>(1);
+    drop(rx);
+    assert!(tx.try_send(1).unwrap_err().is_disconnected());
+    assert!(block_on(tx.send(1)).unwrap_err().is_disconnected());
+    assert!(block_on(tx.reserve()).is_err());
+
+    let (tx, mut rx) = mpsc::bounded::<i32>(0);
+    let mut send = tx.send(1);
+    assert_eq!(send.poll_unpin(noop_local_waker_ref()), Poll::Pending);
+    rx.close();
+    drop(rx);
+    assert!(block_on(send).unwrap_err().is_disconnected());
+}
@@ -521,3 +521,128 @@ fn try_send_recv() {
     rx.try_next().unwrap();
     rx.try_next().unwrap_err(); // should be empty
 }
+
+#[test]
+fn send_and_recv_futures() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+    block_on(tx.send(1)).unwrap();
+    assert_eq!(block_on(rx.recv()), Some(1));
+    drop(tx);
+    assert_eq!(block_on(rx.recv()), None);
+    assert_eq!(block_on(rx.recv()), None);
+
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    tx.unbounded_send(2).unwrap();
+    drop(tx);
+    assert_eq!(block_on(rx.recv()), Some(2));
+    assert_eq!(block_on(rx.recv()), None);
+}
+
+#[test]
+fn blocking_send_recv_threads() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(1);
+
+    let t = thread::spawn(move || {
+        for i in 0..100 {
+            tx.blocking_send(i).unwrap();
+        }
+    });
+
+    for i in 0..100 {
+        assert_eq!(rx.blocking_recv(), Some(i));
+    }
+    assert_eq!(rx.blocking_recv(), None);
+    t.join().unwrap();
+}
+
+#[test]
+fn blocking_send_to_dropped_receiver() {
+    let (mut tx, rx) = mpsc::channel::<i32>(1);
+    drop(rx);
+    assert!(tx.blocking_send(1).unwrap_err().is_disconnected());
+}
+
+#[test]
+fn try_recv_many() {
+    let (tx, mut rx) = mpsc::unbounded::<i32>();
+    let mut buf = Vec::new();
+    assert!(rx.try_recv_many(&mut buf, 10).is_err());
+
+    for i in 0..5 {
+        tx.unbounded_send(i).unwrap();
+    }
+    assert_eq!(rx.try_recv_many(&mut buf, 3), Ok(3));
+    assert_eq!(rx.try_recv_many(&mut buf, 0), Ok(0));
+    assert_eq!(rx.try_recv_many(&mut buf, 10), Ok(2));
+    assert_eq!(buf, vec![0, 1, 2, 3, 4]);
+
+    drop(tx);
+    assert_eq!(rx.try_recv_many(&mut buf, 10), Ok(0));
+    assert_eq!(rx.try_recv_many(&mut buf, 10), Ok(0));
+}
+
+#[test]
+fn poll_recv_many() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(4);
+    let (lw, counter) = futures_test::task::new_count_waker();
+    let mut buf = Vec::new();
+
+    assert_eq!(rx.poll_recv_many(&lw, &mut buf, 10), Poll::Pending);
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(counter, 1);
+
+    assert_eq!(rx.poll_recv_many(&lw, &mut buf, 10), Poll::Ready(2));
+    assert_eq!(buf, vec![1, 2]);
+
+    drop(tx);
+    assert_eq!(rx.poll_recv_many(&lw, &mut buf, 10), Poll::Ready(0));
+}
+
+#[test]
+fn drain_after_close() {
+    let (mut tx, mut rx) = mpsc::channel::<i32>(4);
+    for i in 0..3 {
+        tx.try_send(i).unwrap();
+    }
+    rx.close();
+    assert!(tx.try_send(3).is_err());
+
+    assert_eq!(rx.drain().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(rx.drain().next(), None);
+    assert_eq!(block_on(rx.recv()), None);
+}
+
+#[test]
+fn introspection() {
+    let (mut tx, rx) = mpsc::channel::<i32>(2);
+    let tx2 = tx.clone();
+    assert_eq!(tx.num_senders(), 2);
+    assert_eq!(rx.num_senders(), 2);
+    assert_eq!(tx.capacity(), 4);
+    assert_eq!(rx.capacity(), 4);
+    assert!(tx.is_empty());
+
+    tx.try_send(1).unwrap();
+    tx.try_send(2).unwrap();
+    assert_eq!(tx.len(), 2);
+    assert_eq!(rx.len(), 2);
+
+    assert!(tx.same_receiver(&tx2));
+    assert!(tx.is_connected_to(&rx));
+    let (other_tx, other_rx) = mpsc::channel::<i32>(2);
+    assert!(!tx.same_receiver(&other_tx));
+    assert!(!tx.is_connected_to(&other_rx));
+
+    drop(tx2);
+    assert_eq!(tx.num_senders(), 1);
+    assert_eq!(tx.capacity(), 3);
+    assert_eq!(rx.capacity(), 3);
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(rx.len(), 1);
+    assert_eq!(rx.capacity(), usize::max_value());
+    assert!(tx.is_connected_to(&rx));
+    assert!(tx.same_receiver(&tx.clone()));
+}
@@ -0,0 +1,73 @@
+//! Checks that `futures-channel` builds and works with only its `alloc`
+//! feature enabled.
+
+#![feature(futures_api)]
+#![cfg_attr(not(test), no_std)]
+
+pub use futures_channel::{mpsc, oneshot};
+
+#[cfg(test)]
+mod tests {
+    use futures_channel::{mpsc, oneshot};
+    use futures_core::future::Future;
+    use futures_core::stream::Stream;
+    use futures_core::task::{LocalWaker, Poll};
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{local_waker_from_nonlocal, Wake};
+
+    struct CountWake(AtomicUsize);
+
+    impl Wake for CountWake {
+        fn wake(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn count_waker() -> (LocalWaker, Arc<CountWake>) {
+        let count = Arc::new(CountWake(AtomicUsize::new(0)));
+        (local_waker_from_nonlocal(count.clone()), count)
+    }
+
+    #[test]
+    fn mpsc_parks_and_unparks_sender() {
+        let (lw, count) = count_waker();
+        let (mut tx, mut rx) = mpsc::channel::<i32>(0);
+
+        tx.try_send(1).unwrap();
+        assert!(tx.try_send(2).unwrap_err().is_full());
+        assert!(tx.poll_ready(&lw).is_pending());
+
+        assert_eq!(Pin::new(&mut rx).poll_next(&lw), Poll::Ready(Some(1)));
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert!(tx.poll_ready(&lw).is_ready());
+
+        drop(tx);
+        assert_eq!(Pin::new(&mut rx).poll_next(&lw), Poll::Ready(None));
+    }
+
+    #[test]
+    fn mpsc_unbounded() {
+        let (lw, _) = count_waker();
+        let (tx, mut rx) = mpsc::unbounded::<i32>();
+
+        tx.unbounded_send(1).unwrap();
+        tx.unbounded_send(2).unwrap();
+        drop(tx);
+        assert_eq!(Pin::new(&mut rx).poll_next(&lw), Poll::Ready(Some(1)));
+        assert_eq!(Pin::new(&mut rx).poll_next(&lw), Poll::Ready(Some(2)));
+        assert_eq!(Pin::new(&mut rx).poll_next(&lw), Poll::Ready(None));
+    }
+
+    #[test]
+    fn oneshot() {
+        let (lw, count) = count_waker();
+        let (tx, mut rx) = oneshot::channel::<i32>();
+
+        assert!(Pin::new(&mut rx).poll(&lw).is_pending());
+        tx.send(1).unwrap();
+        assert_eq!(count.0.load(Ordering::SeqCst), 1);
+        assert_eq!(Pin::new(&mut rx).poll(&lw), Poll::Ready(Ok(1)));
+    }
+}
@@ -96,6 +96,61 @@ fn close_wakes() {
     t.join().unwrap();
 }
 
+#[test]
+fn blocking_recv() {
+    let (tx, rx) = oneshot::channel::<i32>();
+    let t = thread::spawn(move || rx.blocking_recv());
+    tx.send(1).unwrap();
+    assert_eq!(t.join().unwrap(), Ok(1));
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    drop(tx);
+    assert_eq!(rx.blocking_recv(), Err(oneshot::Canceled));
+}
+
+#[test]
+fn is_connected_to() {
+    let (tx, rx) = oneshot::channel::<u32>();
+    let (_tx2, rx2) = oneshot::channel::<u32>();
+    assert!(tx.is_connected_to(&rx));
+    assert!(!tx.is_connected_to(&rx2));
+}
+
+#[test]
+fn cancellation_with_reason() {
+    let (mut tx, mut rx) = oneshot::channel::<u32>();
+    assert!(tx.cancel_reason().is_none());
+    let t = thread::spawn(move || {
+        rx.close_with(503u16);
+    });
+    let reason = block_on(tx.cancellation()).unwrap();
+    assert_eq!(reason.downcast_ref::<u16>(), Some(&503));
+    assert!(tx.cancel_reason().is_none());
+    t.join().unwrap();
+
+    let (mut tx, rx) = oneshot::channel::<u32>();
+    drop(rx);
+    assert!(block_on(tx.cancellation()).is_none());
+}
+
+#[test]
+fn receiver_is_fused() {
+    use futures::future::FusedFuture;
+
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    assert!(!rx.is_terminated());
+    assert_eq!(rx.try_recv(), Ok(None));
+    assert!(!rx.is_terminated());
+    tx.send(1).unwrap();
+    assert_eq!(block_on(&mut rx), Ok(1));
+    assert!(rx.is_terminated());
+
+    let (tx, mut rx) = oneshot::channel::<u32>();
+    drop(tx);
+    assert_eq!(rx.try_recv(), Err(oneshot::Canceled));
+    assert!(rx.is_terminated());
+}
+
 #[test]
 fn is_canceled() {
     let (tx, rx) = oneshot::channel::<u32>();
@@ -0,0 +1,151 @@
+#![feature(futures_api)]
+
+use futures::channel::priority;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::thread;
+
+trait AssertSend: Send + Sync {}
+impl AssertSend for priority::Sender<i32> {}
+impl AssertSend for priority::Receiver<i32> {}
+
+#[test]
+fn highest_lane_first() {
+    let (tx, rx) = priority::channel::<i32>(3, 4);
+    tx.try_send_with_priority(0, 1).unwrap();
+    tx.try_send_with_priority(1, 2).unwrap();
+    tx.try_send_with_priority(0, 3).unwrap();
+    tx.try_send_with_priority(2, 4).unwrap();
+    tx.try_send_with_priority(1, 5).unwrap();
+    drop(tx);
+
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![4, 2, 5, 1, 3]);
+}
+
+#[test]
+fn lanes_are_bounded_separately() {
+    let (tx, mut rx) = priority::channel::<i32>(2, 1);
+    tx.try_send_with_priority(0, 1).unwrap();
+    assert!(tx.try_send_with_priority(0, 2).unwrap_err().is_full());
+    tx.try_send_with_priority(1, 3).unwrap();
+
+    let (lw, counter) = new_count_waker();
+    let mut send = tx.send_with_priority(0, 2);
+    assert_eq!(send.poll_unpin(&lw), Poll::Pending);
+
+    // Taking from the high lane doesn't free the low one.
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+    assert_eq!(counter, 0);
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter, 1);
+
+    assert_eq!(send.poll_unpin(&lw), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+}
+
+#[test]
+fn futures_sharing_a_sender_are_all_woken() {
+    let (tx, mut rx) = priority::channel::<i32>(1, 1);
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    tx.try_send_with_priority(0, 1).unwrap();
+    let mut send1 = tx.send_with_priority(0, 2);
+    let mut send2 = tx.send_with_priority(0, 3);
+    assert_eq!(send1.poll_unpin(&lw1), Poll::Pending);
+    assert_eq!(send2.poll_unpin(&lw2), Poll::Pending);
+
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 1);
+    assert_eq!(counter2, 0);
+    assert_eq!(send1.poll_unpin(&lw1), Poll::Ready(Ok(())));
+
+    assert_eq!(rx.try_next().unwrap(), Some(2));
+    assert_eq!(counter2, 1);
+    assert_eq!(send2.poll_unpin(&lw2), Poll::Ready(Ok(())));
+    assert_eq!(rx.try_next().unwrap(), Some(3));
+}
+
+#[test]
+fn dropped_sender_passes_wakeup_on() {
+    let (mut tx1, mut rx) = priority::channel::<i32>(1, 1);
+    let tx2 = tx1.clone();
+    let (lw1, counter1) = new_count_waker();
+    let (lw2, counter2) = new_count_waker();
+
+    tx1.try_send_with_priority(0, 1).unwrap();
+    assert_eq!(tx1.poll_ready(0, &lw1), Poll::Pending);
+    let mut send = tx2.send_with_priority(0, 2);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Pending);
+
+    // `tx1` is woken for the free slot but dropped without taking it.
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(counter1, 1);
+    drop(tx1);
+    assert_eq!(counter2, 1);
+    assert_eq!(send.poll_unpin(&lw2), Poll::Ready(Ok(())));
+}
+
+#[test]
+fn receiver_is_woken() {
+    let (tx, mut rx) = priority::channel::<i32>(2, 1);
+    let (lw, counter) = new_count_waker();
+
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Pending);
+    tx.try_send_with_priority(1, 1).unwrap();
+    assert_eq!(counter, 1);
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Ready(Some(1)));
+
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Pending);
+    drop(tx);
+    assert_eq!(counter, 2);
+    assert_eq!(rx.poll_next_unpin(&lw), Poll::Ready(None));
+    assert_eq!(rx.poll_next_unpin(noop_local_waker_ref()), Poll::Ready(None));
+}
+
+#[test]
+fn close_and_disconnect() {
+    let (tx, mut rx) = priority::channel::<i32>(2, 2);
+    tx.try_send_with_priority(0, 1).unwrap();
+    rx.close();
+    assert!(tx.is_closed());
+    assert!(tx.try_send_with_priority(1, 2).unwrap_err().is_disconnected());
+    assert_eq!(rx.try_next().unwrap(), Some(1));
+    assert_eq!(rx.try_next().unwrap(), None);
+
+    let (tx, rx) = priority::channel::<i32>(2, 2);
+    drop(rx);
+    assert!(block_on(tx.send_with_priority(1, 1)).unwrap_err().is_disconnected());
+}
+
+#[test]
+#[should_panic]
+fn level_out_of_range() {
+    let (tx, _rx) = priority::channel::<i32>(2, 2);
+    let _ = tx.try_send_with_priority(2, 1);
+}
+
+#[test]
+fn send_across_threads() {
+    let (tx, rx) = priority::channel::<i32>(2, 2);
+    let handles: Vec<_> = (0..2)
+        .map(|level| {
+            let tx = tx.clone();
+            thread::spawn(move || {
+                for i in 0..50 {
+                    block_on(tx.send_with_priority(level, i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let received = block_on(rx.collect::<Vec<_>>());
+    assert_eq!(received.len(), 100);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+#![feature(futures_api)]
+
+use futures::channel::watch;
+use futures::executor::block_on;
+use futures::future::FutureExt;
+use futures::stream::{FusedStream, StreamExt};
+use futures::task::Poll;
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::thread;
+
+trait AssertSend: Send + Sync {}
+impl AssertSend for watch::Sender<i32> {}
+impl AssertSend for watch::Receiver<i32> {}
+
+#[test]
+fn borrow_sees_latest_value() {
+    let (tx, rx) = watch::channel("a");
+    assert_eq!(*rx.borrow(), "a");
+
+    tx.send("b").unwrap();
+    tx.send("c").unwrap();
+    assert_eq!(*rx.borrow(), "c");
+    assert_eq!(*tx.borrow(), "c");
+}
+
+#[test]
+fn changed_completes_once_per_change() {
+    let (tx, mut rx) = watch::channel(0);
+    let lw = noop_local_waker_ref();
+
+    assert_eq!(rx.changed().poll_unpin(lw), Poll::Pending);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx.changed().poll_unpin(lw), Poll::Ready(Ok(())));
+    assert_eq!(*rx.borrow(), 2);
+    assert_eq!(rx.changed().poll_unpin(lw), Poll::Pending);
+
+    drop(tx);
+    assert!(rx.changed().poll_unpin(lw).is_ready());
+    assert_eq!(*rx.borrow(), 2);
+}
+
+#[test]
+fn all_receivers_are_woken() {
+    let (tx, mut rx1) = watch::channel(0);
+    let mut rx2 = tx.subscribe();
+    let mut rx3 = rx1.clone();
+    let (lw, counter) = new_count_waker();
+
+    for rx in vec![&mut rx1, &mut rx2, &mut rx3] {
+        assert_eq!(rx.poll_changed(&lw), Poll::Pending);
+    }
+    tx.send(1).unwrap();
+    assert_eq!(counter, 3);
+    for rx in vec![&mut rx1, &mut rx2, &mut rx3] {
+        assert_eq!(rx.poll_changed(&lw), Poll::Ready(Ok(())));
+    }
+}
+
+#[test]
+fn stream_yields_only_on_change() {
+    let (tx, mut rx) = watch::channel(0);
+    let lw = noop_local_waker_ref();
+
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Pending);
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Ready(Some(2)));
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Pending);
+
+    tx.send(3).unwrap();
+    drop(tx);
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Ready(Some(3)));
+    assert_eq!(rx.poll_next_unpin(lw), Poll::Ready(None));
+    assert!(rx.is_terminated());
+}
+
+#[test]
+fn send_without_receivers_fails() {
+    let (tx, rx) = watch::channel(0);
+    drop(rx);
+    assert!(tx.is_closed());
+    assert_eq!(tx.send(1).unwrap_err().into_inner(), 1);
+    assert_eq!(*tx.borrow(), 0);
+}
+
+#[test]
+fn watch_across_threads() {
+    let (tx, mut rx) = watch::channel(0);
+    let handle = thread::spawn(move || {
+        for i in 1..=100 {
+            tx.send(i).unwrap();
+        }
+    });
+
+    block_on(wait_until_closed(&mut rx));
+    handle.join().unwrap();
+    assert_eq!(*rx.borrow(), 100);
+}
+
+fn wait_until_closed(rx: &mut watch::Receiver<i32>) -> impl futures::Future<Output = ()> + '_ {
+    futures::future::poll_fn(move |lw| {
+        loop {
+            match rx.poll_changed(lw) {
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(_)) => return Poll::Ready(()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    })
+}
@@ -231,6 +231,33 @@ mod if_std {
         }
     }
 
+    // A boxed trait object is a fat pointer which doesn't fit into the thin
+    // pointer returned by `into_raw`, so it is boxed once more.
+    macro_rules! unsafe_future_obj_dyn {
+        ($($bounds:tt)*) => {
+            unsafe impl<'a, T> UnsafeFutureObj<'a, T>
+                for Pin<Box<dyn Future<Output = T> $($bounds)* + 'a>>
+            {
+                fn into_raw(self) -> *mut () {
+                    Box::into_raw(Box::new(self)) as *mut ()
+                }
+
+                unsafe fn poll(ptr: *mut (), lw: &LocalWaker) -> Poll<T> {
+                    let ptr = ptr as *mut Self;
+                    (*ptr).as_mut().poll(lw)
+                }
+
+                unsafe fn drop(ptr: *mut ()) {
+                    #[allow(clippy::cast_ptr_alignment)]
+                    drop(Box::from_raw(ptr as *mut Self))
+                }
+            }
+        }
+    }
+
+    unsafe_future_obj_dyn!();
+    unsafe_future_obj_dyn!(+ Send);
+
     impl<'a, F: Future<Output = ()> + Send + 'a> From<Pin<Box<F>>> for FutureObj<'a, ()> {
         fn from(boxed: Pin<Box<F>>) -> Self {
             FutureObj::new(boxed)
@@ -232,6 +232,33 @@ mod if_std {
         }
     }
 
+    // A boxed trait object is a fat pointer which doesn't fit into the thin
+    // pointer returned by `into_raw`, so it is boxed once more.
+    macro_rules! unsafe_stream_obj_dyn {
+        ($($bounds:tt)*) => {
+            unsafe impl<'a, T> UnsafeStreamObj<'a, T>
+                for Pin<Box<dyn Stream<Item = T> $($bounds)* + 'a>>
+            {
+                fn into_raw(self) -> *mut () {
+                    Box::into_raw(Box::new(self)) as *mut ()
+                }
+
+                unsafe fn poll_next(ptr: *mut (), lw: &LocalWaker) -> Poll<Option<T>> {
+                    let ptr = ptr as *mut Self;
+                    (*ptr).as_mut().poll_next(lw)
+                }
+
+                unsafe fn drop(ptr: *mut ()) {
+                    #[allow(clippy::cast_ptr_alignment)]
+                    drop(Box::from_raw(ptr as *mut Self))
+                }
+            }
+        }
+    }
+
+    unsafe_stream_obj_dyn!();
+    unsafe_stream_obj_dyn!(+ Send);
+
     impl<'a, F: Stream<Item = ()> + Send + 'a> From<Pin<Box<F>>> for StreamObj<'a, ()> {
         fn from(boxed: Pin<Box<F>>) -> Self {
             StreamObj::new(boxed)
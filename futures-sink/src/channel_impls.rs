@@ -1,5 +1,6 @@
 use crate::{Sink, Poll};
 use futures_core::task::LocalWaker;
+use futures_channel::{broadcast, mpmc};
 use futures_channel::mpsc::{Sender, SendError, UnboundedSender};
 use std::pin::Pin;
 
@@ -69,3 +70,47 @@ impl<'a, T> Sink for &'a UnboundedSender<T> {
         Poll::Ready(Ok(()))
     }
 }
+
+impl<T> Sink for broadcast::Sender<T> {
+    type SinkItem = T;
+    type SinkError = broadcast::SendError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, msg: T) -> Result<(), Self::SinkError> {
+        self.send(msg)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        self.close_channel();
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Sink for mpmc::Sender<T> {
+    type SinkItem = T;
+    type SinkError = SendError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        mpmc::Sender::poll_ready(&mut *self, lw)
+    }
+
+    fn start_send(self: Pin<&mut Self>, msg: T) -> Result<(), Self::SinkError> {
+        mpmc::Sender::start_send(&*self, msg)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+        self.close_channel();
+        Poll::Ready(Ok(()))
+    }
+}
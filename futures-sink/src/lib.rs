@@ -230,6 +230,36 @@ mod if_std {
             Pin::new(&mut **self).poll_close(lw)
         }
     }
+
+    impl<S: ?Sized + Sink> Sink for Pin<::std::boxed::Box<S>> {
+        type SinkItem = S::SinkItem;
+        type SinkError = S::SinkError;
+
+        fn poll_ready(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+            S::poll_ready((*self).as_mut(), lw)
+        }
+
+        fn start_send(mut self: Pin<&mut Self>, item: Self::SinkItem) -> Result<(), Self::SinkError> {
+            S::start_send((*self).as_mut(), item)
+        }
+
+        fn poll_flush(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+            S::poll_flush((*self).as_mut(), lw)
+        }
+
+        fn poll_close(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Result<(), Self::SinkError>> {
+            S::poll_close((*self).as_mut(), lw)
+        }
+    }
+
+    /// An owned dynamically typed [`Sink`] for use in cases where you can't
+    /// statically type your result or need to add some indirection.
+    pub type BoxSink<'a, Item, E> =
+        Pin<::std::boxed::Box<dyn Sink<SinkItem = Item, SinkError = E> + Send + 'a>>;
+
+    /// `BoxSink`, but without the `Send` requirement.
+    pub type LocalBoxSink<'a, Item, E> =
+        Pin<::std::boxed::Box<dyn Sink<SinkItem = Item, SinkError = E> + 'a>>;
 }
 
 #[cfg(feature = "std")]
@@ -1,14 +1,16 @@
 use crate::task::AtomicWaker;
 use futures_core::future::Future;
+use futures_core::stream::{FusedStream, Stream};
 use futures_core::task::{LocalWaker, Poll};
 use pin_utils::unsafe_pinned;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
-/// A future which can be remotely short-circuited using an `AbortHandle`.
+/// A future or stream which can be remotely short-circuited using an
+/// `AbortHandle`.
 #[derive(Debug, Clone)]
-#[must_use = "futures do nothing unless polled"]
+#[must_use = "futures/streams do nothing unless polled"]
 pub struct Abortable<Fut> {
     future: Fut,
     inner: Arc<AbortInner>,
@@ -16,15 +18,17 @@ pub struct Abortable<Fut> {
 
 impl<Fut: Unpin> Unpin for Abortable<Fut> {}
 
-impl<Fut> Abortable<Fut> where Fut: Future {
+impl<Fut> Abortable<Fut> {
     unsafe_pinned!(future: Fut);
 
-    /// Creates a new `Abortable` future using an existing `AbortRegistration`.
-    /// `AbortRegistration`s can be acquired through `AbortHandle::new`.
+    /// Creates a new `Abortable` future or stream using an existing
+    /// `AbortRegistration`. `AbortRegistration`s can be acquired through
+    /// `AbortHandle::new` or `CancellationToken::abort_registration`.
     ///
     /// When `abort` is called on the handle tied to `reg` or if `abort` has
-    /// already been called, the future will complete immediately without making
-    /// any further progress.
+    /// already been called, the future will complete immediately without
+    /// making any further progress. An aborted stream terminates, yielding
+    /// `None`.
     ///
     /// Example:
     ///
@@ -43,6 +47,14 @@ impl<Fut> Abortable<Fut> where Fut: Future {
             inner: reg.inner,
         }
     }
+
+    /// Checks whether the task has been aborted. Note that all this
+    /// method indicates is whether `abort` was *called*. This means that it
+    /// will return `true` even if the wrapped future or stream has completed
+    /// in the meantime.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.is_aborted()
+    }
 }
 
 /// A registration handle for a `Abortable` future.
@@ -50,13 +62,13 @@ impl<Fut> Abortable<Fut> where Fut: Future {
 /// in calls to `Abortable::new`.
 #[derive(Debug)]
 pub struct AbortRegistration {
-    inner: Arc<AbortInner>,
+    pub(crate) inner: Arc<AbortInner>,
 }
 
 /// A handle to a `Abortable` future.
 #[derive(Debug, Clone)]
 pub struct AbortHandle {
-    inner: Arc<AbortInner>,
+    pub(crate) inner: Arc<AbortInner>,
 }
 
 impl AbortHandle {
@@ -76,10 +88,7 @@ impl AbortHandle {
     /// abort_handle.abort();
     /// assert_eq!(block_on(future), Err(Aborted));
     pub fn new_pair() -> (Self, AbortRegistration) {
-        let inner = Arc::new(AbortInner {
-            waker: AtomicWaker::new(),
-            cancel: AtomicBool::new(false),
-        });
+        let inner = Arc::new(AbortInner::new());
 
         (
             AbortHandle {
@@ -95,18 +104,45 @@ impl AbortHandle {
 // Inner type storing the waker to awaken and a bool indicating that it
 // should be cancelled.
 #[derive(Debug)]
-struct AbortInner {
+pub(crate) struct AbortInner {
     waker: AtomicWaker,
     cancel: AtomicBool,
 }
 
-/// Creates a new `Abortable` future and a `AbortHandle` which can be used to stop it.
+impl AbortInner {
+    pub(crate) fn new() -> AbortInner {
+        AbortInner {
+            waker: AtomicWaker::new(),
+            cancel: AtomicBool::new(false),
+        }
+    }
+
+    pub(crate) fn abort(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        self.waker.wake();
+    }
+
+    pub(crate) fn is_aborted(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    // Registers the current task to be woken by `abort` and returns whether
+    // `abort` has already been called.
+    pub(crate) fn register(&self, lw: &LocalWaker) -> bool {
+        self.waker.register(lw);
+
+        // Checking with `Relaxed` is sufficient because `register` introduces
+        // an `AcqRel` barrier.
+        self.is_aborted()
+    }
+}
+
+/// Creates a new `Abortable` future or stream and a `AbortHandle` which can
+/// be used to stop it.
 ///
 /// This function is a convenient (but less flexible) alternative to calling
 /// `AbortHandle::new` and `Abortable::new` manually.
-pub fn abortable<Fut>(future: Fut) -> (Abortable<Fut>, AbortHandle)
-    where Fut: Future
-{
+pub fn abortable<Fut>(future: Fut) -> (Abortable<Fut>, AbortHandle) {
     let (handle, reg) = AbortHandle::new_pair();
     (
         Abortable::new(future, reg),
@@ -133,20 +169,49 @@ impl<Fut> Future for Abortable<Fut> where Fut: Future {
         }
 
         // Register to receive a wakeup if the future is aborted in the... future
-        self.inner.waker.register(lw);
+        // and check to see if the future was aborted between the first check
+        // and registration.
+        if self.inner.register(lw) {
+            return Poll::Ready(Err(Aborted))
+        }
 
-        // Check to see if the future was aborted between the first check and
-        // registration.
-        // Checking with `Relaxed` is sufficient because `register` introduces an
-        // `AcqRel` barrier.
+        Poll::Pending
+    }
+}
+
+impl<St> Stream for Abortable<St> where St: Stream {
+    type Item = St::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        // Check if the stream has been aborted
         if self.inner.cancel.load(Ordering::Relaxed) {
-            return Poll::Ready(Err(Aborted))
+            return Poll::Ready(None)
+        }
+
+        // attempt to pull the next item from the stream
+        if let Poll::Ready(x) = self.as_mut().future().poll_next(lw) {
+            return Poll::Ready(x)
+        }
+
+        // Register to receive a wakeup if the stream is aborted and check to
+        // see if it was aborted between the first check and registration.
+        if self.inner.register(lw) {
+            return Poll::Ready(None)
         }
 
         Poll::Pending
     }
 }
 
+impl<St> FusedStream for Abortable<St> where St: FusedStream {
+    fn is_terminated(&self) -> bool {
+        self.is_aborted() || self.future.is_terminated()
+    }
+}
+
 impl AbortHandle {
     /// Abort the `Abortable` future associated with this handle.
     ///
@@ -155,7 +220,15 @@ impl AbortHandle {
     /// another thread, it will not immediately stop running. Instead, it will
     /// continue to run until its poll method returns.
     pub fn abort(&self) {
-        self.inner.cancel.store(true, Ordering::Relaxed);
-        self.inner.waker.wake();
+        self.inner.abort();
+    }
+
+    /// Checks whether `abort` has been called on this handle or any of its
+    /// clones.
+    ///
+    /// Note that this will return `true` even if the associated future or
+    /// stream had already completed before it was aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.inner.is_aborted()
     }
 }
@@ -0,0 +1,241 @@
+use super::abortable::{AbortInner, AbortRegistration};
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{LocalWaker, Poll};
+use slab::Slab;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+/// A token which can be used to signal cancellation to a whole tree of tasks.
+///
+/// Child tokens are created with [`child_token`](CancellationToken::child_token).
+/// Cancelling a token cancels all of its children (and their children, and
+/// so on), while cancelling a child leaves its parent untouched. Clones of a
+/// token share the same cancellation state.
+///
+/// Futures and streams can be tied to a token by wrapping them in an
+/// [`Abortable`](super::Abortable) with a registration obtained from
+/// [`abort_registration`](CancellationToken::abort_registration), so a
+/// single call to [`cancel`](CancellationToken::cancel) tears down everything
+/// spawned below that token.
+///
+/// Example:
+///
+/// ```
+/// use futures::future::{self, Abortable, Aborted, CancellationToken};
+/// use futures::executor::block_on;
+///
+/// let parent = CancellationToken::new();
+/// let child = parent.child_token();
+///
+/// let fut = Abortable::new(future::empty::<()>(), child.abort_registration());
+///
+/// parent.cancel();
+/// assert!(child.is_cancelled());
+/// assert_eq!(block_on(fut), Err(Aborted));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CancellationToken {
+    inner: Arc<TokenInner>,
+}
+
+#[derive(Debug)]
+struct TokenInner {
+    cancelled: AtomicBool,
+    // Everything that has to be cancelled along with this token. Emptied once
+    // the token is cancelled.
+    children: Mutex<Slab<Child>>,
+}
+
+// Child tokens are held strongly, so that dropping an intermediate token
+// doesn't cut off the tokens and registrations below it.
+#[derive(Debug)]
+enum Child {
+    Token(Arc<TokenInner>),
+    Abort(Weak<AbortInner>),
+}
+
+impl Child {
+    // Returns `false` if cancelling this child can't have any effect
+    // anymore.
+    fn is_alive(&self) -> bool {
+        match self {
+            Child::Token(token) => {
+                !token.cancelled.load(Ordering::SeqCst) &&
+                    (Arc::strong_count(token) > 1 || token.has_live_children())
+            }
+            Child::Abort(abort) => abort.upgrade().is_some(),
+        }
+    }
+}
+
+impl TokenInner {
+    fn new(cancelled: bool) -> TokenInner {
+        TokenInner {
+            cancelled: AtomicBool::new(cancelled),
+            children: Mutex::new(Slab::new()),
+        }
+    }
+
+    fn cancel(&self) {
+        if self.cancelled.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        // Children added after this point see the flag while holding the
+        // lock and start out cancelled instead of being added.
+        let mut children = mem::replace(&mut *self.children.lock().unwrap(), Slab::new());
+        for child in children.drain() {
+            match child {
+                Child::Token(token) => token.cancel(),
+                Child::Abort(abort) => if let Some(abort) = abort.upgrade() {
+                    abort.abort();
+                },
+            }
+        }
+    }
+
+    fn has_live_children(&self) -> bool {
+        self.children.lock().unwrap().iter().any(|(_, child)| child.is_alive())
+    }
+
+    // Registers `child` to be cancelled along with this token, returning its
+    // key, or `None` if the token has already been cancelled.
+    fn add_child(&self, child: Child) -> Option<usize> {
+        let mut children = self.children.lock().unwrap();
+        if self.cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        // Children which have been dropped or cancelled in the meantime don't
+        // need to be cancelled anymore, so don't let them pile up.
+        if children.len() == children.capacity() {
+            let dead = children.iter()
+                .filter(|(_, child)| !child.is_alive())
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>();
+            for key in dead {
+                children.remove(key);
+            }
+        }
+        Some(children.insert(child))
+    }
+
+    // Creates an abort handle which is aborted when this token is cancelled,
+    // together with its key in `children`.
+    fn add_abort(&self) -> (Arc<AbortInner>, Option<usize>) {
+        let inner = Arc::new(AbortInner::new());
+        let key = self.add_child(Child::Abort(Arc::downgrade(&inner)));
+        if key.is_none() {
+            inner.abort();
+        }
+        (inner, key)
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token which isn't cancelled and has no parent.
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            inner: Arc::new(TokenInner::new(false)),
+        }
+    }
+
+    /// Creates a child token which is cancelled when this token is
+    /// cancelled.
+    ///
+    /// If this token has already been cancelled, the child starts out
+    /// cancelled.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = Arc::new(TokenInner::new(false));
+        if self.inner.add_child(Child::Token(child.clone())).is_none() {
+            child.cancelled.store(true, Ordering::SeqCst);
+        }
+        CancellationToken { inner: child }
+    }
+
+    /// Creates an `AbortRegistration` which is aborted when this token is
+    /// cancelled.
+    ///
+    /// The registration is meant to be passed to `Abortable::new` to tie a
+    /// future or stream to this token.
+    pub fn abort_registration(&self) -> AbortRegistration {
+        let (inner, _) = self.inner.add_abort();
+        AbortRegistration { inner }
+    }
+
+    /// Cancels this token and all of its children.
+    ///
+    /// Cancelling a token more than once has no further effect.
+    pub fn cancel(&self) {
+        self.inner.cancel();
+    }
+
+    /// Returns `true` if this token has been cancelled, either directly or
+    /// through one of its ancestors.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future which completes once this token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        let (inner, key) = self.inner.add_abort();
+        Cancelled {
+            token: self.inner.clone(),
+            inner,
+            key,
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+/// Future for the [`cancelled`](CancellationToken::cancelled) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Cancelled {
+    token: Arc<TokenInner>,
+    inner: Arc<AbortInner>,
+    // Key of `inner` in the token's children, `None` if the token was
+    // already cancelled when this future was created.
+    key: Option<usize>,
+}
+
+impl Drop for Cancelled {
+    fn drop(&mut self) {
+        // Unregister from the token right away, so that polling a fresh
+        // `cancelled()` future in a loop doesn't grow its list of children.
+        // The entry stays alive as long as `inner` does, so the key can only
+        // be gone if the token has been cancelled since.
+        if let Some(key) = self.key {
+            let mut children = self.token.children.lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if children.contains(key) {
+                children.remove(key);
+            }
+        }
+    }
+}
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<()> {
+        if self.inner.is_aborted() || self.inner.register(lw) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl FusedFuture for Cancelled {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_aborted()
+    }
+}
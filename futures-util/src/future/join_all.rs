@@ -1,6 +1,7 @@
 //! Definition of the `JoinAll` combinator, waiting for all of a list of futures
 //! to finish.
 
+use crate::stream::{FuturesUnordered, OrderWrapper, StreamExt};
 use std::fmt;
 use std::future::Future;
 use std::iter::FromIterator;
@@ -8,95 +9,75 @@ use std::mem;
 use std::pin::Pin;
 use std::prelude::v1::*;
 use std::task::Poll;
-
-#[derive(Debug)]
-enum ElemState<F>
-where
-    F: Future,
-{
-    Pending(F),
-    Done(Option<F::Output>),
-}
-
-impl<F> ElemState<F>
-where
-    F: Future,
-{
-    fn pending_pin_mut<'a>(self: Pin<&'a mut Self>) -> Option<Pin<&'a mut F>> {
-        // Safety: Basic enum pin projection, no drop + optionally Unpin based
-        // on the type of this variant
-        match unsafe { self.get_unchecked_mut() } {
-            ElemState::Pending(f) => Some(unsafe { Pin::new_unchecked(f) }),
-            ElemState::Done(_) => None,
-        }
-    }
-
-    fn take_done(self: Pin<&mut Self>) -> Option<F::Output> {
-        // Safety: Going from pin to a variant we never pin-project
-        match unsafe { self.get_unchecked_mut() } {
-            ElemState::Pending(_) => None,
-            ElemState::Done(output) => output.take(),
-        }
-    }
-}
-
-impl<F> Unpin for ElemState<F>
-where
-    F: Future + Unpin,
-{
-}
-
-fn iter_pin_mut<T>(slice: Pin<&mut [T]>) -> impl Iterator<Item = Pin<&mut T>> {
-    // Safety: `std` _could_ make this unsound if it were to decide Pin's
-    // invariants aren't required to transmit through slices. Otherwise this has
-    // the same safety as a normal field pin projection.
-    unsafe { slice.get_unchecked_mut() }
-        .iter_mut()
-        .map(|t| unsafe { Pin::new_unchecked(t) })
-}
+use std::vec;
 
 /// A future which takes a list of futures and resolves with a vector of the
 /// completed values.
 ///
-/// This future is created with the `join_all` function.
+/// This future is created with the `join_all` or `join_all_limited`
+/// functions.
 #[must_use = "futures do nothing unless polled"]
 pub struct JoinAll<F>
 where
     F: Future,
 {
-    elems: Pin<Box<[ElemState<F>]>>,
+    // Futures which haven't been started yet.
+    queued: vec::IntoIter<F>,
+    in_progress: FuturesUnordered<OrderWrapper<F>>,
+    outputs: Vec<Option<F::Output>>,
+    limit: usize,
+    next_index: usize,
 }
 
+// Queued futures are moved into `in_progress` before they are pinned, which
+// keeps them behind a `Box`.
+impl<F: Future> Unpin for JoinAll<F> {}
+
 impl<F> fmt::Debug for JoinAll<F>
 where
-    F: Future + fmt::Debug,
-    F::Output: fmt::Debug,
+    F: Future,
 {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("JoinAll")
-            .field("elems", &self.elems)
+            .field("queued", &self.queued.len())
+            .field("in_progress", &self.in_progress.len())
+            .field("limit", &self.limit)
             .finish()
     }
 }
 
+impl<F: Future> JoinAll<F> {
+    fn new<I>(i: I, limit: usize) -> JoinAll<F>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let queued: Vec<F> = i.into_iter().collect();
+        JoinAll {
+            outputs: queued.iter().map(|_| None).collect(),
+            queued: queued.into_iter(),
+            in_progress: FuturesUnordered::new(),
+            limit,
+            next_index: 0,
+        }
+    }
+}
+
 /// Creates a future which represents a collection of the outputs of the futures
 /// given.
 ///
 /// The returned future will drive execution for all of its underlying futures,
 /// collecting the results into a destination `Vec<T>` in the same order as they
-/// were provided.
+/// were provided. Only the futures that have been woken are polled again, so
+/// this scales to a large number of futures.
 ///
 /// # See Also
 ///
 /// This is purposefully a very simple API for basic use-cases. In a lot of
 /// cases you will want to use the more powerful
-/// [`FuturesUnordered`][crate::stream::FuturesUnordered] APIs, some
-/// examples of additional functionality that provides:
-///
-///  * Adding new futures to the set even after it has been started.
-///
-///  * Only polling the specific futures that have been woken. In cases where
-///    you have a lot of futures this will result in much more efficient polling.
+/// [`FuturesUnordered`][crate::stream::FuturesUnordered] APIs, which for
+/// example allow adding new futures to the set even after it has been
+/// started. To limit how many futures run at the same time, use
+/// [`join_all_limited`].
 ///
 /// # Examples
 ///
@@ -117,8 +98,42 @@ where
     I: IntoIterator,
     I::Item: Future,
 {
-    let elems: Box<[_]> = i.into_iter().map(ElemState::Pending).collect();
-    JoinAll { elems: Box::into_pin(elems) }
+    JoinAll::new(i, usize::max_value())
+}
+
+/// Creates a future which represents a collection of the outputs of the futures
+/// given, running at most `limit` of them at the same time.
+///
+/// This works like [`join_all`], except that the futures are started in the
+/// order they were provided and a future is only started once fewer than
+/// `limit` futures are in progress. This is useful to process a large number
+/// of jobs without overwhelming the resources they use.
+///
+/// # Panics
+///
+/// This function panics if `limit` is zero.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_await, await_macro, futures_api)]
+/// # futures::executor::block_on(async {
+/// use futures::future::join_all_limited;
+///
+/// async fn foo(i: u32) -> u32 { i }
+///
+/// let futures = (1..=100).map(foo);
+///
+/// assert_eq!(await!(join_all_limited(futures, 10)), (1..=100).collect::<Vec<_>>());
+/// # });
+/// ```
+pub fn join_all_limited<I>(i: I, limit: usize) -> JoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+    JoinAll::new(i, limit)
 }
 
 impl<F> Future for JoinAll<F>
@@ -131,26 +146,30 @@ where
         mut self: Pin<&mut Self>,
         lw: &::std::task::LocalWaker,
     ) -> Poll<Self::Output> {
-        let mut all_done = true;
+        let this = &mut *self;
 
-        for mut elem in iter_pin_mut(self.elems.as_mut()) {
-            if let Some(pending) = elem.as_mut().pending_pin_mut() {
-                if let Poll::Ready(output) = pending.poll(lw) {
-                    elem.set(ElemState::Done(Some(output)));
-                } else {
-                    all_done = false;
+        loop {
+            while this.in_progress.len() < this.limit {
+                match this.queued.next() {
+                    Some(future) => {
+                        let index = this.next_index;
+                        this.next_index += 1;
+                        this.in_progress.push(OrderWrapper { data: future, index });
+                    }
+                    None => break,
                 }
             }
-        }
 
-        if all_done {
-            let mut elems = mem::replace(&mut self.elems, Box::pin([]));
-            let result = iter_pin_mut(elems.as_mut())
-                .map(|e| e.take_done().unwrap())
-                .collect();
-            Poll::Ready(result)
-        } else {
-            Poll::Pending
+            match ready!(this.in_progress.poll_next_unpin(lw)) {
+                Some(output) => this.outputs[output.index] = Some(output.data),
+                None => {
+                    let outputs = mem::replace(&mut this.outputs, Vec::new());
+                    let result = outputs.into_iter()
+                        .map(|output| output.unwrap())
+                        .collect();
+                    return Poll::Ready(result)
+                }
+            }
         }
     }
 }
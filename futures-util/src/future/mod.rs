@@ -7,6 +7,16 @@ use core::pin::Pin;
 use futures_core::future::Future;
 use futures_core::stream::Stream;
 use futures_core::task::{LocalWaker, Poll};
+use pin_utils::pin_mut;
+
+#[cfg(feature = "std")]
+use crate::timer::Clock;
+#[cfg(feature = "std")]
+use futures_core::future::{BoxFuture, LocalBoxFuture};
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+pub use either::Either;
 
 // re-export for `select!`
 #[doc(hidden)]
@@ -16,6 +26,9 @@ pub use futures_core::future::FusedFuture;
 mod empty;
 pub use self::empty::{empty, Empty};
 
+mod poll_immediate;
+pub use self::poll_immediate::{poll_immediate, PollImmediate};
+
 mod lazy;
 pub use self::lazy::{lazy, Lazy};
 
@@ -50,9 +63,8 @@ pub use self::join::{Join, Join3, Join4, Join5};
 mod map;
 pub use self::map::Map;
 
-// Todo
-// mod select;
-// pub use self::select::Select;
+mod select;
+pub use self::select::{select, select_biased, Select};
 
 mod then;
 pub use self::then::Then;
@@ -72,6 +84,11 @@ mod abortable;
 #[cfg(feature = "std")]
 pub use self::abortable::{abortable, Abortable, AbortHandle, AbortRegistration, Aborted};
 
+#[cfg(feature = "std")]
+mod cancellation_token;
+#[cfg(feature = "std")]
+pub use self::cancellation_token::{CancellationToken, Cancelled};
+
 #[cfg(feature = "std")]
 mod catch_unwind;
 #[cfg(feature = "std")]
@@ -86,22 +103,22 @@ pub use self::remote_handle::{Remote, RemoteHandle};
 mod join_all;
 
 #[cfg(feature = "std")]
-pub use self::join_all::{join_all, JoinAll};
+pub use self::join_all::{join_all, join_all_limited, JoinAll};
 
-// #[cfg(feature = "std")]
-// mod select_all;
-// #[cfg(feature = "std")]
-// pub use self::select_all::{SelectAll, SelectAllNext, select_all};
-
-// #[cfg(feature = "std")]
-// mod select_ok;
-// #[cfg(feature = "std")]
-// pub use self::select_ok::{SelectOk, select_ok};
+#[cfg(feature = "std")]
+mod select_all;
+#[cfg(feature = "std")]
+pub use self::select_all::{select_all, SelectAll};
 
 #[cfg(feature = "std")]
 mod shared;
 #[cfg(feature = "std")]
-pub use self::shared::Shared;
+pub use self::shared::{Shared, WeakShared};
+
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "std")]
+pub use self::timeout::Timeout;
 
 impl<T: ?Sized> FutureExt for T where T: Future {}
 
@@ -171,50 +188,6 @@ pub trait FutureExt: Future {
         assert_future::<Fut::Output, _>(Then::new(self, f))
     }
 
-    /* TODO
-    /// Waits for either one of two differently-typed futures to complete.
-    ///
-    /// This function will return a new future which awaits for either this or
-    /// the `other` future to complete. The returned future will finish with
-    /// both the value resolved and a future representing the completion of the
-    /// other work.
-    ///
-    /// Note that this function consumes the receiving futures and returns a
-    /// wrapped version of them.
-    ///
-    /// Also note that if both this and the second future have the same
-    /// success/error type you can use the `Either::split` method to
-    /// conveniently extract out the value at the end.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use futures::future::{self, Either};
-    ///
-    /// // A poor-man's join implemented on top of select
-    ///
-    /// fn join<A, B, E>(a: A, b: B) -> Box<Future<Item=(A::Item, B::Item), Error=E>>
-    ///     where A: Future<Error = E> + 'static,
-    ///           B: Future<Error = E> + 'static,
-    ///           E: 'static,
-    /// {
-    ///     Box::new(a.select(b).then(|res| -> Box<Future<Item=_, Error=_>> {
-    ///         match res {
-    ///             Ok(Either::Left((x, b))) => Box::new(b.map(move |y| (x, y))),
-    ///             Ok(Either::Right((y, a))) => Box::new(a.map(move |x| (x, y))),
-    ///             Err(Either::Left((e, _))) => Box::new(future::err(e)),
-    ///             Err(Either::Right((e, _))) => Box::new(future::err(e)),
-    ///         }
-    ///     }))
-    /// }
-    /// ```
-    fn select<B>(self, other: B) -> Select<Self, B::Future>
-        where B: IntoFuture, Self: Sized
-    {
-        select::new(self, other.into_future())
-    }
-    */
-
     /// Joins the result of two futures, waiting for them both to complete.
     ///
     /// This function will return a new future which awaits both this and the
@@ -348,6 +321,37 @@ pub trait FutureExt: Future {
         Join5::new(self, future2, future3, future4, future5)
     }
 
+    /// Waits for either this or the `other` future to complete.
+    ///
+    /// This method returns a new future which resolves with the output of
+    /// whichever future completes first, together with the other future,
+    /// which hasn't completed yet. See [`select()`] for details.
+    ///
+    /// Note that this method consumes the receiving futures and returns a
+    /// wrapped version of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::future::{self, Either, FutureExt};
+    ///
+    /// let a = future::ready(1);
+    /// let b = future::empty::<i32>();
+    ///
+    /// match block_on(a.select(b)) {
+    ///     Either::Left((value, _b)) => assert_eq!(value, 1),
+    ///     Either::Right(_) => panic!("`empty` should never complete"),
+    /// }
+    /// ```
+    fn select<B>(self, other: B) -> Select<Self, B>
+    where
+        B: Future + Unpin,
+        Self: Unpin + Sized,
+    {
+        select(self, other)
+    }
+
     /* ToDo: futures-core cannot implement Future for Either anymore because of
              the orphan rule. Remove? Implement our own `Either`?
     /// Wrap this future in an `Either` future, making it the left-hand variant
@@ -652,14 +656,65 @@ pub trait FutureExt: Future {
         remote_handle::remote_handle(self)
     }
 
-    /// Wrap the future in a Box, pinning it.
+    /// Wrap the future in a Box, pinning it and erasing its type.
+    ///
+    /// This is useful to return futures of different types from the same
+    /// function, e.g. from a trait method. See [`boxed_local`] for futures
+    /// which aren't `Send`.
+    ///
+    /// [`boxed_local`]: FutureExt::boxed_local
     #[cfg(feature = "std")]
-    fn boxed(self) -> Pin<Box<Self>>
-        where Self: Sized
+    fn boxed<'a>(self) -> BoxFuture<'a, Self::Output>
+        where Self: Sized + Send + 'a
+    {
+        Box::pin(self)
+    }
+
+    /// Wrap the future in a Box, pinning it and erasing its type.
+    ///
+    /// Similar to [`boxed`], but without the `Send` requirement.
+    ///
+    /// [`boxed`]: FutureExt::boxed
+    #[cfg(feature = "std")]
+    fn boxed_local<'a>(self) -> LocalBoxFuture<'a, Self::Output>
+        where Self: Sized + 'a
     {
         Box::pin(self)
     }
 
+    /// Requires this future to complete within `dur`.
+    ///
+    /// The returned future resolves to `Ok` with this future's output if it
+    /// completes in time, and to `Err(Elapsed)` once `dur` has passed
+    /// according to `clock` otherwise. Passing `TimerHandle::default()` as
+    /// the clock uses the global timer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::future::{self, FutureExt};
+    /// use futures::timer::{Elapsed, TimerHandle};
+    /// use std::time::Duration;
+    ///
+    /// let clock = TimerHandle::default();
+    /// let fast = future::ready(1).timeout(Duration::from_secs(1), clock.clone());
+    /// assert_eq!(block_on(fast), Ok(1));
+    ///
+    /// let slow = future::empty::<()>().timeout(Duration::from_millis(10), clock);
+    /// assert_eq!(block_on(slow), Err(Elapsed));
+    /// ```
+    #[cfg(feature = "std")]
+    fn timeout<C>(self, dur: Duration, clock: C) -> Timeout<Self, C>
+        where C: Clock,
+              Self: Sized
+    {
+        Timeout::new(self, dur, clock)
+    }
+
     /// Turns a `Future` into a `TryFuture` with `Error = ()`.
     fn unit_error(self) -> UnitError<Self>
         where Self: Sized
@@ -667,6 +722,40 @@ pub trait FutureExt: Future {
         UnitError::new(self)
     }
 
+    /// Evaluates and consumes the future, returning the resulting output if
+    /// the future is ready after the first call to `Future::poll`.
+    ///
+    /// If `poll` instead returns `Poll::Pending`, `None` is returned.
+    ///
+    /// This method is useful in cases where immediacy is more important than
+    /// waiting for a result. It is also convenient for quickly obtaining
+    /// the value of a future that is known to always resolve immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::channel::oneshot;
+    /// use futures::future::{self, FutureExt};
+    ///
+    /// assert_eq!(future::ready(1).now_or_never(), Some(1));
+    ///
+    /// let (tx, rx) = oneshot::channel::<i32>();
+    /// tx.send(2).unwrap();
+    /// assert_eq!(rx.now_or_never(), Some(Ok(2)));
+    /// ```
+    fn now_or_never(self) -> Option<Self::Output>
+        where Self: Sized
+    {
+        let lw = crate::task::noop_local_waker_ref();
+
+        let this = self;
+        pin_mut!(this);
+        match this.poll(lw) {
+            Poll::Ready(output) => Some(output),
+            Poll::Pending => None,
+        }
+    }
+
     /// A convenience for calling `Future::poll` on `Unpin` future types.
     fn poll_unpin(&mut self, lw: &LocalWaker) -> Poll<Self::Output>
         where Self: Unpin + Sized
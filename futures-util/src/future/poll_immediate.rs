@@ -0,0 +1,64 @@
+use crate::task::noop_local_waker_ref;
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::unsafe_pinned;
+
+/// Future for the [`poll_immediate`] function.
+#[derive(Debug, Clone)]
+#[must_use = "futures do nothing unless polled"]
+pub struct PollImmediate<Fut> {
+    future: Option<Fut>,
+}
+
+impl<Fut: Unpin> Unpin for PollImmediate<Fut> {}
+
+impl<Fut> PollImmediate<Fut> {
+    unsafe_pinned!(future: Option<Fut>);
+}
+
+/// Creates a future which resolves to `Some` with the output of `future` if
+/// it is ready when polled, and to `None` otherwise.
+///
+/// `future` is polled exactly once, so this is useful to check whether a
+/// future has already completed without waiting for it. If `future` isn't
+/// ready it is dropped, and its output can't be retrieved anymore.
+///
+/// Like `FutureExt::now_or_never`, `future` is polled with a no-op waker: it
+/// is dropped right after a `Poll::Pending`, so waking the current task on
+/// its behalf would only cause a spurious wakeup.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, poll_immediate};
+///
+/// assert_eq!(block_on(poll_immediate(future::ready(1))), Some(1));
+/// assert_eq!(block_on(poll_immediate(future::empty::<i32>())), None);
+/// ```
+pub fn poll_immediate<Fut: Future>(future: Fut) -> PollImmediate<Fut> {
+    PollImmediate { future: Some(future) }
+}
+
+impl<Fut: Future> FusedFuture for PollImmediate<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.future.is_none()
+    }
+}
+
+impl<Fut: Future> Future for PollImmediate<Fut> {
+    type Output = Option<Fut::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, _: &LocalWaker) -> Poll<Self::Output> {
+        let output = match self.as_mut().future().as_pin_mut() {
+            Some(future) => future.poll(noop_local_waker_ref()),
+            None => panic!("PollImmediate polled after completion"),
+        };
+        self.as_mut().future().set(None);
+        match output {
+            Poll::Ready(output) => Poll::Ready(Some(output)),
+            Poll::Pending => Poll::Ready(None),
+        }
+    }
+}
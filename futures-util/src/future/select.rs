@@ -0,0 +1,121 @@
+use core::pin::Pin;
+use either::Either;
+use futures_core::future::{Future, FusedFuture};
+use futures_core::task::{LocalWaker, Poll};
+
+/// Future for the [`select()`] function.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct Select<A, B> {
+    inner: Option<(A, B)>,
+}
+
+impl<A: Unpin, B: Unpin> Unpin for Select<A, B> {}
+
+/// Waits for either one of two differently-typed futures to complete.
+///
+/// This function will return a new future which awaits for either one of both
+/// futures to complete. The returned future will finish with both the value
+/// resolved and a future representing the completion of the other work.
+///
+/// Note that this function consumes the receiving futures and returns a
+/// wrapped version of them.
+///
+/// Every time the returned future is polled, `future1` is polled before
+/// `future2`, so if both are ready the output of `future1` is returned.
+/// [`select_biased`] is an alias which makes that priority explicit at the
+/// call site.
+///
+/// Both futures must be [`Unpin`], as the unfinished future is handed back to
+/// the caller. Futures that are not `Unpin`, such as the futures returned by
+/// `async fn`s, can be used by pinning them first, either by boxing them with
+/// [`Box::pin`] or by pinning them to the stack with the `pin_mut!` macro from
+/// the `pin_utils` crate and passing the resulting `Pin<&mut F>`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_await, await_macro, futures_api)]
+/// # futures::executor::block_on(async {
+/// use futures::future::{self, Either};
+/// use pin_utils::pin_mut;
+///
+/// // These two futures have different types even though their outputs have
+/// // the same type
+/// let future1 = async { 1 };
+/// let future2 = async { 2 };
+///
+/// // 'select' requires Future + Unpin bounds
+/// pin_mut!(future1);
+/// pin_mut!(future2);
+///
+/// let value = match await!(future::select(future1, future2)) {
+///     Either::Left((value1, _)) => value1,  // `value1` is resolved from `future1`
+///                                           // `_` represents `future2`
+///     Either::Right((value2, _)) => value2, // `value2` is resolved from `future2`
+///                                           // `_` represents `future1`
+/// };
+///
+/// assert!(value == 1 || value == 2);
+/// # });
+/// ```
+pub fn select<A, B>(future1: A, future2: B) -> Select<A, B>
+    where A: Future + Unpin, B: Future + Unpin
+{
+    Select { inner: Some((future1, future2)) }
+}
+
+/// Waits for either one of two differently-typed futures to complete, always
+/// polling the first future first.
+///
+/// This is the same as [`select()`], which already polls `future1` before
+/// `future2`. Using this name documents that the priority is relied upon, e.g.
+/// to let a shutdown signal take precedence over other work.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, Either};
+///
+/// let shutdown = future::ready("shutdown");
+/// let work = future::ready("work");
+///
+/// match block_on(future::select_biased(shutdown, work)) {
+///     Either::Left((value, _)) => assert_eq!(value, "shutdown"),
+///     Either::Right(_) => unreachable!(),
+/// }
+/// ```
+pub fn select_biased<A, B>(future1: A, future2: B) -> Select<A, B>
+    where A: Future + Unpin, B: Future + Unpin
+{
+    select(future1, future2)
+}
+
+impl<A, B> FusedFuture for Select<A, B> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<A, B> Future for Select<A, B>
+where
+    A: Future + Unpin,
+    B: Future + Unpin,
+{
+    type Output = Either<(A::Output, B), (B::Output, A)>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let (mut a, mut b) = self.inner.take().expect("cannot poll Select twice");
+        match Pin::new(&mut a).poll(lw) {
+            Poll::Ready(x) => Poll::Ready(Either::Left((x, b))),
+            Poll::Pending => match Pin::new(&mut b).poll(lw) {
+                Poll::Ready(x) => Poll::Ready(Either::Right((x, a))),
+                Poll::Pending => {
+                    self.inner = Some((a, b));
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
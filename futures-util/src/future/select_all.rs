@@ -0,0 +1,78 @@
+//! Definition of the `SelectAll`, finding the first future in a list that
+//! finishes.
+
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::prelude::v1::*;
+
+use futures_core::future::{Future, FusedFuture};
+use futures_core::task::{LocalWaker, Poll};
+
+/// Future for the [`select_all`] function, waiting for one of any of a list
+/// of futures to complete.
+#[must_use = "futures do nothing unless polled"]
+pub struct SelectAll<Fut> {
+    inner: Vec<Fut>,
+}
+
+impl<Fut: fmt::Debug> fmt::Debug for SelectAll<Fut> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SelectAll")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<Fut: Unpin> Unpin for SelectAll<Fut> {}
+
+/// Creates a new future which will select over a list of futures.
+///
+/// The returned future will wait for any future within `iter` to be ready.
+/// Upon completion the item resolved will be returned, along with the index
+/// of the future that was ready and the list of all the remaining futures.
+///
+/// The futures must be [`Unpin`], as the remaining ones are handed back to the
+/// caller. Futures that are not `Unpin`, such as the futures returned by
+/// `async fn`s, can be used by boxing them with [`Box::pin`] first.
+///
+/// # Panics
+///
+/// This function will panic if the iterator specified contains no items.
+pub fn select_all<I>(iter: I) -> SelectAll<I::Item>
+    where I: IntoIterator,
+          I::Item: Future + Unpin,
+{
+    let ret = SelectAll {
+        inner: iter.into_iter().collect()
+    };
+    assert!(!ret.inner.is_empty());
+    ret
+}
+
+impl<Fut: Future + Unpin> FusedFuture for SelectAll<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<Fut: Future + Unpin> Future for SelectAll<Fut> {
+    type Output = (Fut::Output, usize, Vec<Fut>);
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let item = self.inner.iter_mut().enumerate().filter_map(|(i, f)| {
+            match Pin::new(f).poll(lw) {
+                Poll::Pending => None,
+                Poll::Ready(e) => Some((i, e)),
+            }
+        }).next();
+        match item {
+            Some((idx, res)) => {
+                drop(self.inner.remove(idx));
+                let rest = mem::replace(&mut self.inner, Vec::new());
+                Poll::Ready((res, idx, rest))
+            }
+            None => Poll::Pending,
+        }
+    }
+}
@@ -7,7 +7,7 @@ use std::fmt;
 use std::pin::Pin;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::SeqCst;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, Weak};
 
 /// A future that is cloneable and can be polled in multiple threads.
 /// Use the [`shared`](crate::FutureExt::shared) combinator method to convert
@@ -18,6 +18,9 @@ pub struct Shared<Fut: Future> {
     waker_key: usize,
 }
 
+/// A weak reference to a [`Shared`] that can be upgraded much like an `Arc`.
+pub struct WeakShared<Fut: Future>(Weak<Inner<Fut>>);
+
 struct Inner<Fut: Future> {
     future_or_output: UnsafeCell<FutureOrOutput<Fut>>,
     notifier: Arc<Notifier>,
@@ -41,6 +44,12 @@ impl<Fut: Future> fmt::Debug for Shared<Fut> {
     }
 }
 
+impl<Fut: Future> fmt::Debug for WeakShared<Fut> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("WeakShared").finish()
+    }
+}
+
 impl<Fut: Future> fmt::Debug for Inner<Fut> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Inner").finish()
@@ -87,6 +96,62 @@ impl<Fut: Future> Shared<Fut> {
             waker_key: NULL_WAKER_KEY,
         }
     }
+
+    /// Creates a new [`WeakShared`] for this [`Shared`].
+    ///
+    /// Returns [`None`] if this [`Shared`] has already returned its output
+    /// from [`poll`](Future::poll).
+    pub fn downgrade(&self) -> Option<WeakShared<Fut>> {
+        self.inner.as_ref().map(|inner| WeakShared(Arc::downgrade(inner)))
+    }
+
+    /// Gets the number of strong pointers to this allocation, i.e. the
+    /// number of [`Shared`]s which haven't returned their output yet.
+    ///
+    /// Returns [`None`] if this [`Shared`] has already returned its output
+    /// from [`poll`](Future::poll).
+    pub fn strong_count(&self) -> Option<usize> {
+        self.inner.as_ref().map(Arc::strong_count)
+    }
+
+    /// Gets the number of [`WeakShared`]s pointing to this allocation.
+    ///
+    /// Returns [`None`] if this [`Shared`] has already returned its output
+    /// from [`poll`](Future::poll).
+    pub fn weak_count(&self) -> Option<usize> {
+        self.inner.as_ref().map(Arc::weak_count)
+    }
+
+    /// Returns `true` if the two [`Shared`]s point to the same future, in a
+    /// similar vein to `Arc::ptr_eq`.
+    ///
+    /// Returns `false` if either [`Shared`] has already returned its output
+    /// from [`poll`](Future::poll).
+    pub fn ptr_eq(&self, rhs: &Self) -> bool {
+        match (self.inner.as_ref(), rhs.inner.as_ref()) {
+            (Some(lhs), Some(rhs)) => Arc::ptr_eq(lhs, rhs),
+            _ => false,
+        }
+    }
+}
+
+impl<Fut: Future> WeakShared<Fut> {
+    /// Attempts to upgrade this [`WeakShared`] into a [`Shared`].
+    ///
+    /// Returns [`None`] once every [`Shared`] pointing to the future has
+    /// been dropped or has returned its output.
+    pub fn upgrade(&self) -> Option<Shared<Fut>> {
+        Some(Shared {
+            inner: Some(self.0.upgrade()?),
+            waker_key: NULL_WAKER_KEY,
+        })
+    }
+}
+
+impl<Fut: Future> Clone for WeakShared<Fut> {
+    fn clone(&self) -> Self {
+        WeakShared(self.0.clone())
+    }
 }
 
 impl<Fut> Shared<Fut>
@@ -0,0 +1,67 @@
+use crate::future::FutureExt;
+use crate::timer::{Clock, Elapsed};
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::time::Duration;
+
+/// Future for the [`timeout`](super::FutureExt::timeout) method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Timeout<Fut, C: Clock> {
+    future: Fut,
+    delay: Option<C::Delay>,
+}
+
+impl<Fut: Unpin, C: Clock> Unpin for Timeout<Fut, C> {}
+
+impl<Fut: Future, C: Clock> Timeout<Fut, C> {
+    unsafe_pinned!(future: Fut);
+    unsafe_unpinned!(delay: Option<C::Delay>);
+
+    pub(super) fn new(future: Fut, dur: Duration, clock: C) -> Timeout<Fut, C> {
+        let delay = clock.delay_at(clock.now() + dur);
+        Timeout { future, delay: Some(delay) }
+    }
+
+    /// Acquires a reference to the underlying future that this combinator
+    /// is wrapping.
+    pub fn get_ref(&self) -> &Fut {
+        &self.future
+    }
+
+    /// Acquires a mutable reference to the underlying future that this
+    /// combinator is wrapping.
+    pub fn get_mut(&mut self) -> &mut Fut {
+        &mut self.future
+    }
+
+    /// Consumes this combinator, returning the underlying future.
+    pub fn into_inner(self) -> Fut {
+        self.future
+    }
+}
+
+impl<Fut: Future, C: Clock> FusedFuture for Timeout<Fut, C> {
+    fn is_terminated(&self) -> bool {
+        self.delay.is_none()
+    }
+}
+
+impl<Fut: Future, C: Clock> Future for Timeout<Fut, C> {
+    type Output = Result<Fut::Output, Elapsed>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        if let Poll::Ready(output) = self.as_mut().future().poll(lw) {
+            *self.as_mut().delay() = None;
+            return Poll::Ready(Ok(output));
+        }
+
+        let delay = self.as_mut().delay().as_mut()
+            .expect("Timeout polled after completion");
+        ready!(delay.poll_unpin(lw));
+        *self.as_mut().delay() = None;
+        Poll::Ready(Err(Elapsed))
+    }
+}
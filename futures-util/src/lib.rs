@@ -91,3 +91,6 @@ pub mod io;
 
 #[cfg(feature = "std")]
 pub mod lock;
+
+#[cfg(feature = "std")]
+pub mod timer;
@@ -8,6 +8,9 @@ use futures_core::future::Future;
 use futures_core::stream::Stream;
 use futures_sink::Sink;
 
+#[cfg(feature = "std")]
+use futures_sink::{BoxSink, LocalBoxSink};
+
 #[cfg(feature = "compat")]
 use crate::compat::Compat;
 
@@ -249,6 +252,30 @@ pub trait SinkExt: Sink {
         Either::Right(self)
     }
 
+    /// Wrap the sink in a Box, pinning it and erasing its type.
+    ///
+    /// See [`boxed_local`] for sinks which aren't `Send`.
+    ///
+    /// [`boxed_local`]: SinkExt::boxed_local
+    #[cfg(feature = "std")]
+    fn boxed<'a>(self) -> BoxSink<'a, Self::SinkItem, Self::SinkError>
+        where Self: Sized + core::marker::Send + 'a
+    {
+        Box::pin(self)
+    }
+
+    /// Wrap the sink in a Box, pinning it and erasing its type.
+    ///
+    /// Similar to [`boxed`], but without the `Send` requirement.
+    ///
+    /// [`boxed`]: SinkExt::boxed
+    #[cfg(feature = "std")]
+    fn boxed_local<'a>(self) -> LocalBoxSink<'a, Self::SinkItem, Self::SinkError>
+        where Self: Sized + 'a
+    {
+        Box::pin(self)
+    }
+
     /// Wraps a [`Sink`] into a sink compatible with libraries using
     /// futures 0.1 `Sink`. Requires the `compat` feature to be enabled.
     #[cfg(feature = "compat")]
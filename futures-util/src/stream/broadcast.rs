@@ -0,0 +1,302 @@
+use crate::task::local_waker_ref_from_nonlocal;
+use futures_channel::broadcast::Lagged;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll, Wake, Waker};
+use slab::Slab;
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, TryLockError};
+
+/// How a [`Broadcast`] stream deals with subscribers that fall behind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagPolicy {
+    /// Once the buffer is full, the oldest item is dropped to make room for
+    /// a new one. Subscribers which haven't seen the dropped items yet
+    /// receive a [`Lagged`] error with the number of missed items.
+    DropOldest,
+    /// Once the buffer is full, the source stream isn't polled again until
+    /// the slowest subscriber has caught up, so no subscriber ever misses an
+    /// item.
+    Backpressure,
+}
+
+/// A stream that can be cloned to let many subscribers receive every item of
+/// a single underlying stream.
+///
+/// Use the [`broadcast`](crate::StreamExt::broadcast) combinator method to
+/// convert any stream into a `Broadcast` stream.
+#[must_use = "streams do nothing unless polled"]
+pub struct Broadcast<St: Stream> {
+    inner: Arc<Inner<St>>,
+    // Sequence number of the next item this subscriber will receive.
+    pos: u64,
+    key: usize,
+    waker_key: usize,
+}
+
+struct Inner<St: Stream> {
+    // Locked only by the subscriber polling the underlying stream, so the
+    // others can keep taking buffered items meanwhile. `None` once the stream
+    // has completed.
+    stream: Mutex<Option<Pin<Box<St>>>>,
+    state: Mutex<State<St>>,
+    notifier: Arc<Notifier>,
+}
+
+struct State<St: Stream> {
+    // `true` once the stream has completed.
+    done: bool,
+    buffer: VecDeque<St::Item>,
+    // Sequence number of the first item in `buffer`.
+    head: u64,
+    capacity: usize,
+    policy: LagPolicy,
+    // Positions of all subscribers, used to find the slowest one.
+    positions: Slab<u64>,
+}
+
+struct Notifier {
+    wakers: Mutex<Slab<Option<Waker>>>,
+}
+
+impl<St: Stream> fmt::Debug for Broadcast<St> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Broadcast")
+            .field("pos", &self.pos)
+            .finish()
+    }
+}
+
+impl<St: Stream> State<St> {
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+
+    // Drops all buffered items that every subscriber has already received.
+    fn trim(&mut self) {
+        let min = self.positions.iter().map(|(_, pos)| *pos).min();
+        let min = min.unwrap_or_else(|| self.tail());
+        while self.head < min && self.buffer.pop_front().is_some() {
+            self.head += 1;
+        }
+    }
+}
+
+impl<St: Stream> Broadcast<St> {
+    pub(super) fn new(stream: St, capacity: usize, policy: LagPolicy) -> Broadcast<St> {
+        assert!(capacity > 0, "broadcast capacity must be greater than zero");
+
+        let inner = Arc::new(Inner {
+            stream: Mutex::new(Some(Box::pin(stream))),
+            state: Mutex::new(State {
+                done: false,
+                buffer: VecDeque::with_capacity(capacity),
+                head: 0,
+                capacity,
+                policy,
+                positions: Slab::new(),
+            }),
+            notifier: Arc::new(Notifier {
+                wakers: Mutex::new(Slab::new()),
+            }),
+        });
+
+        Broadcast::at(inner, 0)
+    }
+
+    fn at(inner: Arc<Inner<St>>, pos: u64) -> Broadcast<St> {
+        let key = inner.state.lock().unwrap().positions.insert(pos);
+        let waker_key = inner.notifier.wakers.lock().unwrap().insert(None);
+        Broadcast { inner, pos, key, waker_key }
+    }
+
+    /// Creates a new subscriber which receives every item produced from now
+    /// on.
+    ///
+    /// Unlike [`clone`](Clone::clone), which starts the new subscriber at the
+    /// same position as this one, the returned subscriber doesn't receive
+    /// items that are already buffered.
+    pub fn subscribe(&self) -> Broadcast<St> {
+        let tail = self.inner.state.lock().unwrap().tail();
+        Broadcast::at(self.inner.clone(), tail)
+    }
+
+    /// Returns the number of buffered items this subscriber hasn't received
+    /// yet.
+    pub fn len(&self) -> usize {
+        let state = self.inner.state.lock().unwrap();
+        (state.tail() - self.pos.max(state.head)) as usize
+    }
+
+    /// Returns `true` if this subscriber has received every buffered item.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of subscribers of the underlying stream.
+    pub fn subscriber_count(&self) -> usize {
+        self.inner.state.lock().unwrap().positions.len()
+    }
+
+    /// Registers the current task to receive a wakeup when a new item is
+    /// available.
+    fn set_waker(&self, lw: &LocalWaker) {
+        let mut wakers = self.inner.notifier.wakers.lock().unwrap();
+        let waker_slot = &mut wakers[self.waker_key];
+        let needs_replacement = if let Some(old_waker) = waker_slot {
+            !lw.will_wake_nonlocal(old_waker)
+        } else {
+            true
+        };
+        if needs_replacement {
+            *waker_slot = Some(lw.clone().into_waker());
+        }
+    }
+}
+
+impl<St: Stream> FusedStream for Broadcast<St> {
+    fn is_terminated(&self) -> bool {
+        let state = self.inner.state.lock().unwrap();
+        state.done && self.pos >= state.tail()
+    }
+}
+
+impl<St> Stream for Broadcast<St>
+where
+    St: Stream,
+    St::Item: Clone,
+{
+    type Item = Result<St::Item, Lagged>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        this.set_waker(lw);
+
+        let mut state = this.inner.state.lock().unwrap();
+        loop {
+            if this.pos < state.head {
+                let missed = state.head - this.pos;
+                this.pos = state.head;
+                state.positions[this.key] = this.pos;
+                return Poll::Ready(Some(Err(Lagged(missed))));
+            }
+
+            if this.pos < state.tail() {
+                let item = state.buffer[(this.pos - state.head) as usize].clone();
+                this.pos += 1;
+                state.positions[this.key] = this.pos;
+
+                // We may have been the slowest subscriber holding back the
+                // others, so give them a chance to make progress.
+                if state.policy == LagPolicy::Backpressure
+                    && state.buffer.len() >= state.capacity
+                {
+                    state.trim();
+                    drop(state);
+                    this.inner.notifier.wake_all();
+                }
+                return Poll::Ready(Some(Ok(item)));
+            }
+
+            if state.policy == LagPolicy::Backpressure
+                && state.buffer.len() >= state.capacity
+            {
+                state.trim();
+                if state.buffer.len() >= state.capacity {
+                    // Wait for the slowest subscriber to catch up.
+                    return Poll::Pending;
+                }
+            }
+
+            if state.done {
+                return Poll::Ready(None);
+            }
+            drop(state);
+
+            // Only one subscriber polls the underlying stream at a time. The
+            // others wait for it to wake them, as it does once it has an item
+            // or once the stream wakes the notifier.
+            let mut stream = match this.inner.stream.try_lock() {
+                Ok(stream) => stream,
+                Err(TryLockError::WouldBlock) => return Poll::Pending,
+                Err(TryLockError::Poisoned(_)) => panic!("broadcast stream panicked"),
+            };
+            let notifier = this.inner.notifier.clone();
+            let waker = local_waker_ref_from_nonlocal(&notifier);
+            let res = match stream.as_mut() {
+                Some(stream) => stream.as_mut().poll_next(&waker),
+                None => Poll::Ready(None),
+            };
+
+            state = this.inner.state.lock().unwrap();
+            match res {
+                Poll::Ready(Some(item)) => {
+                    if state.buffer.len() >= state.capacity {
+                        state.buffer.pop_front();
+                        state.head += 1;
+                    }
+                    state.buffer.push_back(item);
+                }
+                Poll::Ready(None) => {
+                    *stream = None;
+                    state.done = true;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+
+            // Wake the other subscribers without holding any lock, as their
+            // wakers may poll them right away.
+            drop(state);
+            drop(stream);
+            notifier.wake_all();
+            state = this.inner.state.lock().unwrap();
+        }
+    }
+}
+
+impl<St: Stream> Clone for Broadcast<St> {
+    fn clone(&self) -> Self {
+        Broadcast::at(self.inner.clone(), self.pos)
+    }
+}
+
+impl<St: Stream> Drop for Broadcast<St> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.inner.state.lock() {
+            state.positions.remove(self.key);
+        }
+        if let Ok(mut wakers) = self.inner.notifier.wakers.lock() {
+            wakers.remove(self.waker_key);
+        }
+        // A subscriber waiting for backpressure to be relieved may have been
+        // waiting on us.
+        self.inner.notifier.wake_all();
+    }
+}
+
+impl Notifier {
+    fn wake_all(&self) {
+        let wakers: Vec<Waker> = match self.wakers.lock() {
+            Ok(mut wakers) => wakers.iter_mut()
+                .filter_map(|(_key, opt_waker)| opt_waker.take())
+                .collect(),
+            Err(_) => return,
+        };
+        // Woken subscribers may be polled right away, which registers their
+        // waker again.
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+impl Wake for Notifier {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.wake_all();
+    }
+}
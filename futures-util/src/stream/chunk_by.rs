@@ -0,0 +1,110 @@
+use crate::stream::Fuse;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::mem;
+use std::pin::Pin;
+use std::prelude::v1::*;
+
+/// An adaptor that groups consecutive elements with the same key in a vector.
+///
+/// This adaptor will buffer up items of the stream as long as they map to
+/// the same key and pass on the key together with the vector used for
+/// buffering when the key changes. This is created by the `Stream::chunk_by`
+/// method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ChunkBy<St: Stream, F, K> {
+    stream: Fuse<St>,
+    f: F,
+    group: Option<(K, Vec<St::Item>)>,
+}
+
+impl<St: Unpin + Stream, F, K> Unpin for ChunkBy<St, F, K> {}
+
+impl<St, F, K> ChunkBy<St, F, K>
+    where St: Stream,
+          F: FnMut(&St::Item) -> K,
+          K: PartialEq,
+{
+    unsafe_pinned!(stream: Fuse<St>);
+    unsafe_unpinned!(f: F);
+    unsafe_unpinned!(group: Option<(K, Vec<St::Item>)>);
+
+    pub(super) fn new(stream: St, f: F) -> ChunkBy<St, F, K> {
+        ChunkBy {
+            stream: super::Fuse::new(stream),
+            f,
+            group: None,
+        }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream.into_inner()
+    }
+}
+
+impl<St, F, K> FusedStream for ChunkBy<St, F, K>
+    where St: Stream,
+{
+    fn is_terminated(&self) -> bool {
+        self.group.is_none() && self.stream.is_terminated()
+    }
+}
+
+impl<St, F, K> Stream for ChunkBy<St, F, K>
+    where St: Stream,
+          F: FnMut(&St::Item) -> K,
+          K: PartialEq,
+{
+    type Item = (K, Vec<St::Item>);
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.as_mut().stream().poll_next(lw)) {
+                // Push the item into the current group if its key matches.
+                // Otherwise start a new group and return the finished one.
+                Some(item) => {
+                    let key = (self.as_mut().f())(&item);
+                    match self.as_mut().group() {
+                        Some((current, items)) if *current == key => {
+                            items.push(item);
+                        }
+                        group => {
+                            let finished = mem::replace(group, Some((key, vec![item])));
+                            if finished.is_some() {
+                                return Poll::Ready(finished);
+                            }
+                        }
+                    }
+                }
+
+                // Since the underlying stream ran out of values, return the
+                // last group, if we have one.
+                None => return Poll::Ready(self.as_mut().group().take()),
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::future::FutureExt;
+use crate::stream::Fuse;
+use crate::timer::Clock;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::mem;
+use std::prelude::v1::*;
+use std::time::Duration;
+
+/// Stream for the [`chunks_timeout`](super::StreamExt::chunks_timeout)
+/// method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ChunksTimeout<St: Stream, C: Clock> {
+    stream: Fuse<St>,
+    clock: C,
+    dur: Duration,
+    items: Vec<St::Item>,
+    // Started when the first item of a chunk arrives.
+    delay: Option<C::Delay>,
+}
+
+impl<St: Stream + Unpin, C: Clock> Unpin for ChunksTimeout<St, C> {}
+
+impl<St: Stream, C: Clock> ChunksTimeout<St, C> {
+    unsafe_pinned!(stream: Fuse<St>);
+    unsafe_unpinned!(items: Vec<St::Item>);
+    unsafe_unpinned!(delay: Option<C::Delay>);
+
+    pub(super) fn new(
+        stream: St,
+        capacity: usize,
+        dur: Duration,
+        clock: C,
+    ) -> ChunksTimeout<St, C> {
+        assert!(capacity > 0);
+
+        ChunksTimeout {
+            stream: super::Fuse::new(stream),
+            clock,
+            dur,
+            items: Vec::with_capacity(capacity),
+            delay: None,
+        }
+    }
+
+    fn take(mut self: Pin<&mut Self>) -> Vec<St::Item> {
+        *self.as_mut().delay() = None;
+        let cap = self.items.capacity();
+        mem::replace(self.as_mut().items(), Vec::with_capacity(cap))
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream.into_inner()
+    }
+}
+
+impl<St: Stream, C: Clock> FusedStream for ChunksTimeout<St, C> {
+    fn is_terminated(&self) -> bool {
+        self.items.is_empty() && self.stream.is_terminated()
+    }
+}
+
+impl<St: Stream, C: Clock> Stream for ChunksTimeout<St, C> {
+    type Item = Vec<St::Item>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        let cap = self.items.capacity();
+        loop {
+            match self.as_mut().stream().poll_next(lw) {
+                Poll::Ready(Some(item)) => {
+                    if self.items.is_empty() {
+                        let delay = self.clock.delay_at(self.clock.now() + self.dur);
+                        *self.as_mut().delay() = Some(delay);
+                    }
+                    self.as_mut().items().push(item);
+                    if self.items.len() >= cap {
+                        return Poll::Ready(Some(self.as_mut().take()))
+                    }
+                }
+
+                // Since the underlying stream ran out of values, return what we
+                // have buffered, if we have anything.
+                Poll::Ready(None) => {
+                    let last = if self.items.is_empty() {
+                        None
+                    } else {
+                        Some(self.as_mut().take())
+                    };
+
+                    return Poll::Ready(last);
+                }
+
+                Poll::Pending => break,
+            }
+        }
+
+        match self.as_mut().delay() {
+            Some(delay) => ready!(delay.poll_unpin(lw)),
+            None => return Poll::Pending,
+        }
+        Poll::Ready(Some(self.as_mut().take()))
+    }
+}
@@ -0,0 +1,98 @@
+use crate::future::FutureExt;
+use crate::stream::Fuse;
+use crate::timer::Clock;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::time::Duration;
+
+/// Stream for the [`debounce`](super::StreamExt::debounce) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Debounce<St: Stream, C: Clock> {
+    stream: Fuse<St>,
+    clock: C,
+    dur: Duration,
+    // The latest item together with the delay after which it is yielded.
+    pending: Option<(St::Item, C::Delay)>,
+}
+
+impl<St: Stream + Unpin, C: Clock> Unpin for Debounce<St, C> {}
+
+impl<St: Stream, C: Clock> Debounce<St, C> {
+    unsafe_pinned!(stream: Fuse<St>);
+    unsafe_unpinned!(pending: Option<(St::Item, C::Delay)>);
+
+    pub(super) fn new(stream: St, dur: Duration, clock: C) -> Debounce<St, C> {
+        Debounce {
+            stream: super::Fuse::new(stream),
+            clock,
+            dur,
+            pending: None,
+        }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream.into_inner()
+    }
+}
+
+impl<St: Stream, C: Clock> FusedStream for Debounce<St, C> {
+    fn is_terminated(&self) -> bool {
+        self.pending.is_none() && self.stream.is_terminated()
+    }
+}
+
+impl<St: Stream, C: Clock> Stream for Debounce<St, C> {
+    type Item = St::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St::Item>> {
+        // Only the latest of all items that are ready is kept, restarting the
+        // delay each time.
+        loop {
+            match self.as_mut().stream().poll_next(lw) {
+                Poll::Ready(Some(item)) => {
+                    let delay = self.clock.delay_at(self.clock.now() + self.dur);
+                    *self.as_mut().pending() = Some((item, delay));
+                }
+                // The stream ended, so no newer item can replace the pending
+                // one anymore.
+                Poll::Ready(None) => {
+                    let item = self.as_mut().pending().take().map(|(item, _)| item);
+                    return Poll::Ready(item);
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        match self.as_mut().pending() {
+            Some((_, delay)) => ready!(delay.poll_unpin(lw)),
+            None => return Poll::Pending,
+        }
+        let item = self.as_mut().pending().take().map(|(item, _)| item);
+        Poll::Ready(item)
+    }
+}
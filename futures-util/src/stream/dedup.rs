@@ -0,0 +1,162 @@
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+
+/// A stream combinator which removes consecutive repeated items.
+///
+/// This structure is produced by the `Stream::dedup` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Dedup<St: Stream> {
+    stream: St,
+    last: Option<St::Item>,
+}
+
+impl<St: Stream + Unpin> Unpin for Dedup<St> {}
+
+impl<St> Dedup<St>
+    where St: Stream,
+          St::Item: PartialEq + Clone,
+{
+    unsafe_pinned!(stream: St);
+    unsafe_unpinned!(last: Option<St::Item>);
+
+    pub(super) fn new(stream: St) -> Dedup<St> {
+        Dedup { stream, last: None }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream
+    }
+}
+
+impl<St: Stream + FusedStream> FusedStream for Dedup<St> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+impl<St> Stream for Dedup<St>
+    where St: Stream,
+          St::Item: PartialEq + Clone,
+{
+    type Item = St::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St::Item>> {
+        loop {
+            let item = match ready!(self.as_mut().stream().poll_next(lw)) {
+                Some(item) => item,
+                None => return Poll::Ready(None),
+            };
+            if self.last.as_ref() != Some(&item) {
+                *self.as_mut().last() = Some(item.clone());
+                return Poll::Ready(Some(item));
+            }
+        }
+    }
+}
+
+/// A stream combinator which removes consecutive items mapping to the same
+/// key.
+///
+/// This structure is produced by the `Stream::dedup_by_key` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DedupByKey<St, F, K> {
+    stream: St,
+    f: F,
+    last: Option<K>,
+}
+
+impl<St: Unpin, F, K> Unpin for DedupByKey<St, F, K> {}
+
+impl<St, F, K> DedupByKey<St, F, K>
+    where St: Stream,
+          F: FnMut(&St::Item) -> K,
+          K: PartialEq,
+{
+    unsafe_pinned!(stream: St);
+    unsafe_unpinned!(f: F);
+    unsafe_unpinned!(last: Option<K>);
+
+    pub(super) fn new(stream: St, f: F) -> DedupByKey<St, F, K> {
+        DedupByKey { stream, f, last: None }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream
+    }
+}
+
+impl<St: FusedStream, F, K> FusedStream for DedupByKey<St, F, K> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+impl<St, F, K> Stream for DedupByKey<St, F, K>
+    where St: Stream,
+          F: FnMut(&St::Item) -> K,
+          K: PartialEq,
+{
+    type Item = St::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St::Item>> {
+        loop {
+            let item = match ready!(self.as_mut().stream().poll_next(lw)) {
+                Some(item) => item,
+                None => return Poll::Ready(None),
+            };
+            let key = (self.as_mut().f())(&item);
+            if self.last.as_ref() != Some(&key) {
+                *self.as_mut().last() = Some(key);
+                return Poll::Ready(Some(item));
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::stream::{FlattenUnordered, Map, StreamExt};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::unsafe_pinned;
+use core::fmt;
+use core::pin::Pin;
+
+/// Maps a stream such that each item becomes a stream, and polls up to
+/// `limit` of the resulting streams concurrently.
+///
+/// This combinator is created by the `Stream::flat_map_unordered` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct FlatMapUnordered<St, U, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{
+    inner: FlattenUnordered<Map<St, F>>,
+}
+
+impl<St, U, F> Unpin for FlatMapUnordered<St, U, F>
+where
+    St: Stream + Unpin,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{}
+
+impl<St, U, F> fmt::Debug for FlatMapUnordered<St, U, F>
+where
+    St: Stream + fmt::Debug,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FlatMapUnordered")
+            .field("stream", self.get_ref())
+            .finish()
+    }
+}
+
+impl<St, U, F> FlatMapUnordered<St, U, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{
+    unsafe_pinned!(inner: FlattenUnordered<Map<St, F>>);
+
+    pub(super) fn new(stream: St, limit: Option<usize>, f: F) -> FlatMapUnordered<St, U, F> {
+        FlatMapUnordered {
+            inner: FlattenUnordered::new(stream.map(f), limit),
+        }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.inner.get_ref().get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.inner.get_mut().get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.inner.into_inner().into_inner()
+    }
+}
+
+impl<St, U, F> Stream for FlatMapUnordered<St, U, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{
+    type Item = U::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        self.inner().poll_next(lw)
+    }
+}
+
+impl<St, U, F> FusedStream for FlatMapUnordered<St, U, F>
+where
+    St: Stream,
+    F: FnMut(St::Item) -> U,
+    U: Stream + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.inner.is_terminated()
+    }
+}
@@ -0,0 +1,159 @@
+use crate::stream::{Fuse, FuturesUnordered, StreamExt, StreamFuture};
+use core::num::NonZeroUsize;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use futures_sink::Sink;
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use core::fmt;
+use core::pin::Pin;
+
+/// A combinator used to flatten a stream-of-streams into one stream of
+/// elements, polling up to `limit` inner streams concurrently.
+///
+/// This combinator is created by the `Stream::flatten_unordered` method.
+#[must_use = "streams do nothing unless polled"]
+pub struct FlattenUnordered<St>
+where
+    St: Stream,
+    St::Item: Stream + Unpin,
+{
+    stream: Fuse<St>,
+    inner_streams: FuturesUnordered<StreamFuture<St::Item>>,
+    limit: Option<NonZeroUsize>,
+}
+
+impl<St> Unpin for FlattenUnordered<St>
+where
+    St: Stream + Unpin,
+    St::Item: Stream + Unpin,
+{}
+
+impl<St> fmt::Debug for FlattenUnordered<St>
+where
+    St: Stream + fmt::Debug,
+    St::Item: Stream + Unpin,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FlattenUnordered")
+            .field("stream", &self.stream)
+            .field("inner_streams", &self.inner_streams)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<St> FlattenUnordered<St>
+where
+    St: Stream,
+    St::Item: Stream + Unpin,
+{
+    unsafe_pinned!(stream: Fuse<St>);
+    unsafe_unpinned!(inner_streams: FuturesUnordered<StreamFuture<St::Item>>);
+
+    pub(super) fn new(stream: St, limit: Option<usize>) -> FlattenUnordered<St> {
+        FlattenUnordered {
+            stream: super::Fuse::new(stream),
+            inner_streams: FuturesUnordered::new(),
+            // Note: `limit` = 0 gets ignored.
+            limit: limit.and_then(NonZeroUsize::new),
+        }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.stream.get_mut()
+    }
+
+    /// Acquires a pinned mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    #[allow(clippy::needless_lifetimes)] // https://github.com/rust-lang/rust/issues/52675
+    pub fn get_pin_mut<'a>(self: Pin<&'a mut Self>) -> Pin<&'a mut St> {
+        unsafe { Pin::map_unchecked_mut(self, |x| x.get_mut()) }
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream.into_inner()
+    }
+}
+
+impl<St> Stream for FlattenUnordered<St>
+where
+    St: Stream,
+    St::Item: Stream + Unpin,
+{
+    type Item = <St::Item as Stream>::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            // First up, pull in as many inner streams as the limit allows.
+            while self.limit.map(|limit| limit.get() > self.inner_streams.len()).unwrap_or(true) {
+                match self.as_mut().stream().poll_next(lw) {
+                    Poll::Ready(Some(stream)) => {
+                        self.as_mut().inner_streams().push(stream.into_future())
+                    }
+                    Poll::Ready(None) | Poll::Pending => break,
+                }
+            }
+
+            // Then yield the first item produced by any of the inner streams,
+            // putting the rest of that stream back into the set.
+            match self.as_mut().inner_streams().poll_next_unpin(lw) {
+                Poll::Ready(Some((Some(item), remaining))) => {
+                    self.as_mut().inner_streams().push(remaining.into_future());
+                    return Poll::Ready(Some(item));
+                }
+                // An inner stream has completed, which may have made room for
+                // another one.
+                Poll::Ready(Some((None, _))) => {}
+                Poll::Ready(None) | Poll::Pending => {
+                    if self.stream.is_done() && self.inner_streams.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+impl<St> FusedStream for FlattenUnordered<St>
+where
+    St: Stream,
+    St::Item: Stream + Unpin,
+{
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated() && self.inner_streams.is_empty()
+    }
+}
+
+// Forwarding impl of Sink from the underlying stream
+impl<S> Sink for FlattenUnordered<S>
+where
+    S: Stream + Sink,
+    S::Item: Stream + Unpin,
+{
+    type SinkItem = S::SinkItem;
+    type SinkError = S::SinkError;
+
+    delegate_sink!(stream);
+}
@@ -11,9 +11,9 @@ use std::pin::Pin;
 
 #[must_use = "futures do nothing unless polled"]
 #[derive(Debug)]
-struct OrderWrapper<T> {
-    data: T, // A future or a future's output
-    index: usize,
+pub(crate) struct OrderWrapper<T> {
+    pub(crate) data: T, // A future or a future's output
+    pub(crate) index: usize,
 }
 
 impl<T> PartialEq for OrderWrapper<T> {
@@ -0,0 +1,70 @@
+use crate::stream::{StreamExt, Fuse};
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+
+/// An adapter for strictly alternating the items of two streams.
+///
+/// This combinator is created by the `Stream::interleave` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Interleave<St1, St2> {
+    stream1: Fuse<St1>,
+    stream2: Fuse<St2>,
+    second_next: bool,
+}
+
+impl<St1: Unpin, St2: Unpin> Unpin for Interleave<St1, St2> {}
+
+impl<St1, St2> Interleave<St1, St2>
+    where St1: Stream,
+          St2: Stream<Item = St1::Item>
+{
+    unsafe_pinned!(stream1: Fuse<St1>);
+    unsafe_pinned!(stream2: Fuse<St2>);
+    unsafe_unpinned!(second_next: bool);
+
+    pub(super) fn new(stream1: St1, stream2: St2) -> Interleave<St1, St2> {
+        Interleave {
+            stream1: stream1.fuse(),
+            stream2: stream2.fuse(),
+            second_next: false,
+        }
+    }
+}
+
+impl<St1, St2> FusedStream for Interleave<St1, St2> {
+    fn is_terminated(&self) -> bool {
+        self.stream1.is_terminated() && self.stream2.is_terminated()
+    }
+}
+
+impl<St1, St2> Stream for Interleave<St1, St2>
+    where St1: Stream,
+          St2: Stream<Item = St1::Item>
+{
+    type Item = St1::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker
+    ) -> Poll<Option<St1::Item>> {
+        // Unlike `Select`, we wait for the stream whose turn it is instead of
+        // yielding whichever stream is ready. Only once that stream has
+        // completed do we fall back to the other one.
+        if self.second_next {
+            if let Some(item) = ready!(self.as_mut().stream2().poll_next(lw)) {
+                *self.as_mut().second_next() = false;
+                return Poll::Ready(Some(item));
+            }
+            self.as_mut().stream1().poll_next(lw)
+        } else {
+            if let Some(item) = ready!(self.as_mut().stream1().poll_next(lw)) {
+                *self.as_mut().second_next() = true;
+                return Poll::Ready(Some(item));
+            }
+            self.as_mut().stream2().poll_next(lw)
+        }
+    }
+}
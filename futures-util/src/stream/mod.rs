@@ -25,6 +25,9 @@ pub use self::collect::Collect;
 mod concat;
 pub use self::concat::Concat;
 
+mod dedup;
+pub use self::dedup::{Dedup, DedupByKey};
+
 mod empty;
 pub use self::empty::{empty, Empty};
 
@@ -55,6 +58,9 @@ pub use self::into_future::StreamFuture;
 mod inspect;
 pub use self::inspect::Inspect;
 
+mod interleave;
+pub use self::interleave::Interleave;
+
 mod map;
 pub use self::map::Map;
 
@@ -73,9 +79,15 @@ pub use self::peek::Peekable;
 mod poll_fn;
 pub use self::poll_fn::{poll_fn, PollFn};
 
+mod poll_immediate;
+pub use self::poll_immediate::{poll_immediate, PollImmediate};
+
 mod select;
 pub use self::select::Select;
 
+mod select_with_strategy;
+pub use self::select_with_strategy::{select_with_strategy, PollNext, SelectWithStrategy};
+
 mod skip;
 pub use self::skip::Skip;
 
@@ -94,11 +106,30 @@ pub use self::then::Then;
 mod unfold;
 pub use self::unfold::{unfold, Unfold};
 
+mod unzip;
+pub use self::unzip::Unzip;
+
 mod zip;
 pub use self::zip::Zip;
 
+mod zip_longest;
+pub use self::zip_longest::{EitherOrBoth, ZipLongest};
+
 #[cfg(feature = "std")]
 use std;
+#[cfg(feature = "std")]
+use std::time::Duration;
+#[cfg(feature = "std")]
+use crate::timer::Clock;
+#[cfg(feature = "std")]
+use futures_core::stream::{BoxStream, LocalBoxStream};
+
+#[cfg(feature = "std")]
+mod broadcast;
+#[cfg(feature = "std")]
+pub use self::broadcast::{Broadcast, LagPolicy};
+#[cfg(feature = "std")]
+pub use futures_channel::broadcast::Lagged;
 
 #[cfg(feature = "std")]
 mod buffer_unordered;
@@ -115,11 +146,36 @@ mod catch_unwind;
 #[cfg(feature = "std")]
 pub use self::catch_unwind::CatchUnwind;
 
+#[cfg(feature = "std")]
+mod chunk_by;
+#[cfg(feature = "std")]
+pub use self::chunk_by::ChunkBy;
+
 #[cfg(feature = "std")]
 mod chunks;
 #[cfg(feature = "std")]
 pub use self::chunks::Chunks;
 
+#[cfg(feature = "std")]
+mod chunks_timeout;
+#[cfg(feature = "std")]
+pub use self::chunks_timeout::ChunksTimeout;
+
+#[cfg(feature = "std")]
+mod debounce;
+#[cfg(feature = "std")]
+pub use self::debounce::Debounce;
+
+#[cfg(feature = "std")]
+mod flatten_unordered;
+#[cfg(feature = "std")]
+pub use self::flatten_unordered::FlattenUnordered;
+
+#[cfg(feature = "std")]
+mod flat_map_unordered;
+#[cfg(feature = "std")]
+pub use self::flat_map_unordered::FlatMapUnordered;
+
 #[cfg(feature = "std")]
 mod for_each_concurrent;
 #[cfg(feature = "std")]
@@ -129,6 +185,8 @@ pub use self::for_each_concurrent::ForEachConcurrent;
 mod futures_ordered;
 #[cfg(feature = "std")]
 pub use self::futures_ordered::{futures_ordered, FuturesOrdered};
+#[cfg(feature = "std")]
+pub(crate) use self::futures_ordered::OrderWrapper;
 
 #[cfg(feature = "std")]
 mod futures_unordered;
@@ -143,7 +201,17 @@ pub use self::split::{SplitStream, SplitSink, ReuniteError};
 #[cfg(feature = "std")]
 mod select_all;
 #[cfg(feature = "std")]
-pub use self::select_all::{select_all, SelectAll};
+pub use self::select_all::{select_all, select_all_biased, SelectAll};
+
+#[cfg(feature = "std")]
+mod throttle;
+#[cfg(feature = "std")]
+pub use self::throttle::Throttle;
+
+#[cfg(feature = "std")]
+mod timeout;
+#[cfg(feature = "std")]
+pub use self::timeout::Timeout;
 
 impl<T: ?Sized> StreamExt for T where T: Stream {}
 
@@ -367,6 +435,35 @@ pub trait StreamExt: Stream {
         Collect::new(self)
     }
 
+    /// Converts a stream of pairs into a future, which
+    /// resolves to pair of containers.
+    ///
+    /// `unzip()` produces a future, which resolves to two
+    /// collections: one from the left elements of the pairs,
+    /// and one from the right elements.
+    ///
+    /// The returned future will be resolved when the stream terminates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+    ///
+    /// let (numbers, letters): (Vec<_>, Vec<_>) = block_on(stream.unzip());
+    /// assert_eq!(numbers, vec![1, 2, 3]);
+    /// assert_eq!(letters, vec!['a', 'b', 'c']);
+    /// ```
+    fn unzip<A, B, FromA, FromB>(self) -> Unzip<Self, FromA, FromB>
+        where FromA: Default + Extend<A>,
+              FromB: Default + Extend<B>,
+              Self: Sized + Stream<Item = (A, B)>,
+    {
+        Unzip::new(self)
+    }
+
     /// Concatenate all items of a stream into a single extendable
     /// destination, returning a future representing the end result.
     ///
@@ -471,6 +568,100 @@ pub trait StreamExt: Stream {
         Flatten::new(self)
     }
 
+    /// Flattens a stream of streams into just one continuous stream, polling
+    /// the inner streams concurrently.
+    ///
+    /// In contrast to [`flatten`](StreamExt::flatten), which exhausts each
+    /// inner stream before moving on to the next one, this combinator polls
+    /// up to `limit` inner streams at a time and yields their items in the
+    /// order in which they become available. Inner streams are only polled
+    /// when they generate wake-up notifications, just like the futures in a
+    /// [`FuturesUnordered`].
+    ///
+    /// The `limit` argument is of type `Into<Option<usize>>`, and so can be
+    /// provided as either `None`, `Some(10)`, or just `10`. Note: a limit of
+    /// zero is interpreted as no limit at all, and will have the same result
+    /// as passing in `None`.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::channel::mpsc;
+    /// use futures::executor::block_on;
+    /// use futures::stream::StreamExt;
+    /// use std::thread;
+    ///
+    /// let (tx1, rx1) = mpsc::unbounded();
+    /// let (tx2, rx2) = mpsc::unbounded();
+    /// let (tx3, rx3) = mpsc::unbounded();
+    ///
+    /// thread::spawn(move || {
+    ///     tx1.unbounded_send(1).unwrap();
+    ///     tx1.unbounded_send(2).unwrap();
+    /// });
+    /// thread::spawn(move || {
+    ///     tx2.unbounded_send(3).unwrap();
+    ///     tx2.unbounded_send(4).unwrap();
+    /// });
+    /// thread::spawn(move || {
+    ///     tx3.unbounded_send(rx1).unwrap();
+    ///     tx3.unbounded_send(rx2).unwrap();
+    /// });
+    ///
+    /// let mut output = block_on(rx3.flatten_unordered(None).collect::<Vec<i32>>());
+    /// output.sort();
+    ///
+    /// assert_eq!(output, vec![1, 2, 3, 4]);
+    /// ```
+    #[cfg(feature = "std")]
+    fn flatten_unordered(self, limit: impl Into<Option<usize>>) -> FlattenUnordered<Self>
+        where Self::Item: Stream + Unpin,
+              Self: Sized
+    {
+        FlattenUnordered::new(self, limit.into())
+    }
+
+    /// Maps a stream like [`map`](StreamExt::map) but flattens the streams
+    /// returned by the closure, polling up to `limit` of them concurrently.
+    ///
+    /// This is equivalent to `self.map(f).flatten_unordered(limit)`; see
+    /// [`flatten_unordered`](StreamExt::flatten_unordered) for details on how
+    /// the items of the inner streams are yielded and how `limit` is
+    /// interpreted.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(1..=3);
+    /// let stream = stream.flat_map_unordered(2, |x| stream::iter(vec![x; x]));
+    ///
+    /// let mut values = block_on(stream.collect::<Vec<_>>());
+    /// values.sort();
+    ///
+    /// assert_eq!(vec![1, 2, 2, 3, 3, 3], values);
+    /// ```
+    #[cfg(feature = "std")]
+    fn flat_map_unordered<U, F>(
+        self,
+        limit: impl Into<Option<usize>>,
+        f: F,
+    ) -> FlatMapUnordered<Self, U, F>
+        where U: Stream + Unpin,
+              F: FnMut(Self::Item) -> U,
+              Self: Sized
+    {
+        FlatMapUnordered::new(self, limit.into(), f)
+    }
+
     /// Skip elements on this stream while the provided asynchronous predicate
     /// resolves to `true`.
     ///
@@ -790,10 +981,71 @@ pub trait StreamExt: Stream {
         CatchUnwind::new(self)
     }
 
-    /// Wrap the stream in a Box, pinning it.
+    /// Create a cloneable handle to this stream which lets many subscribers
+    /// receive every item it produces.
+    ///
+    /// Each clone of the returned [`Broadcast`] is a subscriber which
+    /// receives every item produced after its creation, wrapped in `Ok`.
+    /// A clone starts at the same position as the subscriber it was cloned
+    /// from, while [`Broadcast::subscribe`] creates a subscriber which only
+    /// receives items produced from then on. Whichever subscriber is polled
+    /// drives the underlying stream, so the items are only cloned, never
+    /// recomputed.
+    ///
+    /// Up to `capacity` items are buffered for subscribers which haven't
+    /// received them yet. What happens once the buffer is full depends on
+    /// `policy`: with [`LagPolicy::DropOldest`] the oldest item is dropped and
+    /// subscribers which missed it receive an `Err(Lagged(n))` item next,
+    /// while with [`LagPolicy::Backpressure`] the underlying stream isn't
+    /// polled again until the slowest subscriber has caught up.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, LagPolicy, StreamExt};
+    ///
+    /// let stream = stream::iter(1..=3).broadcast(4, LagPolicy::Backpressure);
+    /// let subscriber = stream.clone();
+    ///
+    /// assert_eq!(block_on(stream.collect::<Vec<_>>()), vec![Ok(1), Ok(2), Ok(3)]);
+    /// assert_eq!(block_on(subscriber.collect::<Vec<_>>()), vec![Ok(1), Ok(2), Ok(3)]);
+    /// ```
     #[cfg(feature = "std")]
-    fn boxed(self) -> Pin<Box<Self>>
-        where Self: Sized
+    fn broadcast(self, capacity: usize, policy: LagPolicy) -> Broadcast<Self>
+        where Self: Sized,
+              Self::Item: Clone,
+    {
+        Broadcast::new(self, capacity, policy)
+    }
+
+    /// Wrap the stream in a Box, pinning it and erasing its type.
+    ///
+    /// See [`boxed_local`] for streams which aren't `Send`.
+    ///
+    /// [`boxed_local`]: StreamExt::boxed_local
+    #[cfg(feature = "std")]
+    fn boxed<'a>(self) -> BoxStream<'a, Self::Item>
+        where Self: Sized + Send + 'a
+    {
+        Box::pin(self)
+    }
+
+    /// Wrap the stream in a Box, pinning it and erasing its type.
+    ///
+    /// Similar to [`boxed`], but without the `Send` requirement.
+    ///
+    /// [`boxed`]: StreamExt::boxed
+    #[cfg(feature = "std")]
+    fn boxed_local<'a>(self) -> LocalBoxStream<'a, Self::Item>
+        where Self: Sized + 'a
     {
         Box::pin(self)
     }
@@ -889,6 +1141,37 @@ pub trait StreamExt: Stream {
         Zip::new(self, other)
     }
 
+    /// An adapter for zipping two streams together until both have completed.
+    ///
+    /// Unlike [`zip`](StreamExt::zip), which ends as soon as either stream
+    /// ends, the zipped stream keeps going until both streams have ended. Each
+    /// item is an [`EitherOrBoth`] which holds both items while both streams
+    /// are active, and only the item of the remaining stream afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, EitherOrBoth, StreamExt};
+    ///
+    /// let stream1 = stream::iter(1..=3);
+    /// let stream2 = stream::iter(5..=6);
+    ///
+    /// let vec = block_on(stream1.zip_longest(stream2)
+    ///                           .collect::<Vec<_>>());
+    /// assert_eq!(vec![
+    ///     EitherOrBoth::Both(1, 5),
+    ///     EitherOrBoth::Both(2, 6),
+    ///     EitherOrBoth::Left(3),
+    /// ], vec);
+    /// ```
+    fn zip_longest<St>(self, other: St) -> ZipLongest<Self, St>
+        where St: Stream,
+              Self: Sized,
+    {
+        ZipLongest::new(self, other)
+    }
+
     /// Adapter for chaining two stream.
     ///
     /// The resulting stream emits elements from the first stream, and when
@@ -952,6 +1235,176 @@ pub trait StreamExt: Stream {
         Chunks::new(self, capacity)
     }
 
+    /// An adaptor for chunking up items of the stream inside a vector, which
+    /// also yields a partial chunk once `dur` has passed since its first item
+    /// arrived.
+    ///
+    /// This works like [`chunks`](StreamExt::chunks), except that items
+    /// don't wait longer than `dur` (according to `clock`) to be passed on
+    /// when the stream produces them slowly. Passing `TimerHandle::default()`
+    /// as the clock uses the global timer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic of `capacity` is zero.
+    #[cfg(feature = "std")]
+    fn chunks_timeout<C>(
+        self,
+        capacity: usize,
+        dur: Duration,
+        clock: C,
+    ) -> ChunksTimeout<Self, C>
+        where C: Clock,
+              Self: Sized
+    {
+        ChunksTimeout::new(self, capacity, dur, clock)
+    }
+
+    /// Requires every item of this stream to arrive within `dur`.
+    ///
+    /// The returned stream yields `Ok` with each item of this stream. If no
+    /// item arrives within `dur` (according to `clock`) after the previous
+    /// one was yielded, `Err(Elapsed)` is yielded instead and the deadline
+    /// starts over, so the stream can be polled again to keep waiting.
+    /// Passing `TimerHandle::default()` as the clock uses the global timer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    fn timeout<C>(self, dur: Duration, clock: C) -> Timeout<Self, C>
+        where C: Clock,
+              Self: Sized
+    {
+        Timeout::new(self, dur, clock)
+    }
+
+    /// Limits this stream to yield at most one item per `period`.
+    ///
+    /// After an item has been yielded the next one is held back until
+    /// `period` has passed according to `clock`. No items are dropped.
+    /// Passing `TimerHandle::default()` as the clock uses the global timer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    fn throttle<C>(self, period: Duration, clock: C) -> Throttle<Self, C>
+        where C: Clock,
+              Self: Sized
+    {
+        Throttle::new(self, period, clock)
+    }
+
+    /// Yields an item only once this stream has produced no newer item for
+    /// `dur`.
+    ///
+    /// Every item restarts the delay and replaces the item waiting to be
+    /// yielded, so a burst of items results in only the last one of the
+    /// burst being yielded once the stream has been quiet for `dur`
+    /// according to `clock`. When this stream ends, the waiting item is
+    /// yielded right away. Passing `TimerHandle::default()` as the clock
+    /// uses the global timer.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    #[cfg(feature = "std")]
+    fn debounce<C>(self, dur: Duration, clock: C) -> Debounce<Self, C>
+        where C: Clock,
+              Self: Sized
+    {
+        Debounce::new(self, dur, clock)
+    }
+
+    /// An adaptor for grouping consecutive items of the stream with the same
+    /// key inside a vector.
+    ///
+    /// The closure `f` is called on every item to compute its key. Items are
+    /// buffered as long as their key is equal to the key of the previous item.
+    /// Once an item with a different key arrives, the key of the buffered
+    /// items is yielded together with them, and a new group is started with
+    /// the new item. When the underlying stream ends, the last group is
+    /// yielded before the stream ends as well.
+    ///
+    /// Note that only consecutive items are grouped, so the same key may be
+    /// yielded more than once.
+    ///
+    /// This method is only available when the `std` feature of this
+    /// library is activated, and it is activated by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![1, 3, 2, 4, 5]);
+    /// let stream = stream.chunk_by(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(
+    ///     vec![(false, vec![1, 3]), (true, vec![2, 4]), (false, vec![5])],
+    ///     block_on(stream.collect::<Vec<_>>()),
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    fn chunk_by<K, F>(self, f: F) -> ChunkBy<Self, F, K>
+        where F: FnMut(&Self::Item) -> K,
+              K: PartialEq,
+              Self: Sized
+    {
+        ChunkBy::new(self, f)
+    }
+
+    /// Removes consecutive repeated items of the stream.
+    ///
+    /// An item is only yielded if it isn't equal to the previously yielded
+    /// item. The last yielded item is cloned in order to compare it to the
+    /// next one; use [`dedup_by_key`](StreamExt::dedup_by_key) to compare a
+    /// key derived from each item instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![1, 1, 2, 3, 3, 3, 1]);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 1], block_on(stream.dedup().collect::<Vec<_>>()));
+    /// ```
+    fn dedup(self) -> Dedup<Self>
+        where Self::Item: PartialEq + Clone,
+              Self: Sized
+    {
+        Dedup::new(self)
+    }
+
+    /// Removes consecutive items of the stream which map to the same key.
+    ///
+    /// The closure `f` is called on every item to compute its key, and an item
+    /// is only yielded if its key isn't equal to the key of the previously
+    /// yielded item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream = stream::iter(vec![10, 11, 20, 21, 22, 30]);
+    /// let stream = stream.dedup_by_key(|x| x / 10);
+    ///
+    /// assert_eq!(vec![10, 20, 30], block_on(stream.collect::<Vec<_>>()));
+    /// ```
+    fn dedup_by_key<K, F>(self, f: F) -> DedupByKey<Self, F, K>
+        where F: FnMut(&Self::Item) -> K,
+              K: PartialEq,
+              Self: Sized
+    {
+        DedupByKey::new(self, f)
+    }
+
     /// This combinator will attempt to pull items from both streams. Each
     /// stream will be polled in a round-robin fashion, and whenever a stream is
     /// ready to yield an item that item is yielded.
@@ -969,6 +1422,38 @@ pub trait StreamExt: Stream {
         Select::new(self, other)
     }
 
+    /// An adapter for strictly alternating the items of two streams.
+    ///
+    /// The resulting stream yields an item from this stream, then one from
+    /// `other`, then one from this stream again, and so on. In contrast to
+    /// [`select`](StreamExt::select) the order doesn't depend on which stream
+    /// is ready first: if it's the turn of a stream which isn't ready yet,
+    /// the interleaved stream waits for it.
+    ///
+    /// Once one of the streams completes the remaining items of the other
+    /// stream are yielded. The returned stream completes when both input
+    /// streams have completed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::stream::{self, StreamExt};
+    ///
+    /// let stream1 = stream::iter(vec![1, 3, 5, 7]);
+    /// let stream2 = stream::iter(vec![2, 4]);
+    ///
+    /// let vec = block_on(stream1.interleave(stream2)
+    ///                           .collect::<Vec<_>>());
+    /// assert_eq!(vec![1, 2, 3, 4, 5, 7], vec);
+    /// ```
+    fn interleave<St>(self, other: St) -> Interleave<Self, St>
+        where St: Stream<Item = Self::Item>,
+              Self: Sized,
+    {
+        Interleave::new(self, other)
+    }
+
     /// A future that completes after the given stream has been fully processed
     /// into the sink, including flushing.
     ///
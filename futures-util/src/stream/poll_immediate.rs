@@ -0,0 +1,80 @@
+use core::pin::Pin;
+use futures_core::stream::Stream;
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::unsafe_pinned;
+
+/// Stream for the [`poll_immediate`] function.
+#[derive(Debug, Clone)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PollImmediate<St> {
+    stream: St,
+}
+
+impl<St: Unpin> Unpin for PollImmediate<St> {}
+
+impl<St> PollImmediate<St> {
+    unsafe_pinned!(stream: St);
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    pub fn get_mut(&mut self) -> &mut St {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    pub fn into_inner(self) -> St {
+        self.stream
+    }
+}
+
+/// Creates a stream which yields the items of `stream` that are ready, and
+/// ends as soon as `stream` would have to wait for the next one.
+///
+/// Whenever `stream` returns `Poll::Pending`, the returned stream returns
+/// `Poll::Ready(None)` instead. Unlike an ordinary stream it may be polled
+/// again afterwards to pick up items that have become ready in the meantime,
+/// which makes it useful to drain everything that is currently buffered.
+///
+/// # Examples
+///
+/// ```
+/// use futures::channel::mpsc;
+/// use futures::executor::block_on;
+/// use futures::stream::{poll_immediate, StreamExt};
+///
+/// let (tx, rx) = mpsc::unbounded();
+/// tx.unbounded_send(1).unwrap();
+/// tx.unbounded_send(2).unwrap();
+///
+/// let mut ready = poll_immediate(rx);
+/// assert_eq!(block_on(ready.by_ref().collect::<Vec<i32>>()), vec![1, 2]);
+///
+/// tx.unbounded_send(3).unwrap();
+/// assert_eq!(block_on(ready.collect::<Vec<i32>>()), vec![3]);
+/// ```
+pub fn poll_immediate<St: Stream>(stream: St) -> PollImmediate<St> {
+    PollImmediate { stream }
+}
+
+impl<St: Stream> Stream for PollImmediate<St> {
+    type Item = St::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St::Item>> {
+        // Unlike `future::poll_immediate`, the stream is kept around after a
+        // `Poll::Pending`, so it is polled with the task's waker rather than a
+        // no-op one: the task can then be woken once more items are ready.
+        match self.stream().poll_next(lw) {
+            Poll::Ready(item) => Poll::Ready(item),
+            Poll::Pending => Poll::Ready(None),
+        }
+    }
+}
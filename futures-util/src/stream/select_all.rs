@@ -17,12 +17,32 @@ use crate::stream::{StreamExt, StreamFuture, FuturesUnordered};
 /// yielded as they become ready. Streams will only be polled when they
 /// generate notifications. This allows to coordinate a large number of streams.
 ///
+/// If some streams should take precedence over others, a set created with
+/// [`SelectAll::biased`] always polls the streams in the order they were
+/// pushed and yields the first available item, so earlier streams preempt
+/// later ones. A set created with [`SelectAll::round_robin`] polls in push
+/// order as well, but starts after the stream that yielded last so that every
+/// stream gets a fair share. Both of these poll every stream on each call to
+/// `poll_next`, so they are best suited for a small number of streams.
+///
 /// Note that you can create a ready-made `SelectAll` via the
 /// `select_all` function in the `stream` module, or you can start with an
 /// empty set with the `SelectAll::new` constructor.
 #[must_use = "streams do nothing unless polled"]
 pub struct SelectAll<St> {
-    inner: FuturesUnordered<StreamFuture<St>>,
+    inner: Inner<St>,
+}
+
+enum Inner<St> {
+    Unordered(FuturesUnordered<StreamFuture<St>>),
+    Ordered {
+        streams: Vec<St>,
+        // Index of the stream to poll first, only advanced in round-robin
+        // mode.
+        next: usize,
+        round_robin: bool,
+        is_terminated: bool,
+    },
 }
 
 impl<St: Debug> Debug for SelectAll<St> {
@@ -37,19 +57,55 @@ impl<St: Stream + Unpin> SelectAll<St> {
     /// The returned `SelectAll` does not contain any streams and, in this
     /// state, `SelectAll::poll` will return `Poll::Ready(None)`.
     pub fn new() -> SelectAll<St> {
-        SelectAll { inner: FuturesUnordered::new() }
+        SelectAll { inner: Inner::Unordered(FuturesUnordered::new()) }
+    }
+
+    /// Constructs a new, empty `SelectAll` which prefers earlier streams.
+    ///
+    /// Every call to `poll_next` polls the contained streams in the order in
+    /// which they were pushed and yields the first item that is available.
+    /// A stream pushed later will therefore only be able to yield an item
+    /// when all streams pushed before it are pending.
+    pub fn biased() -> SelectAll<St> {
+        SelectAll::ordered(false)
+    }
+
+    /// Constructs a new, empty `SelectAll` which polls its streams in turn.
+    ///
+    /// Every call to `poll_next` polls the contained streams in the order in
+    /// which they were pushed, starting with the stream after the one that
+    /// yielded the previous item.
+    pub fn round_robin() -> SelectAll<St> {
+        SelectAll::ordered(true)
+    }
+
+    fn ordered(round_robin: bool) -> SelectAll<St> {
+        SelectAll {
+            inner: Inner::Ordered {
+                streams: Vec::new(),
+                next: 0,
+                round_robin,
+                is_terminated: false,
+            },
+        }
     }
 
     /// Returns the number of streams contained in the set.
     ///
     /// This represents the total number of in-flight streams.
     pub fn len(&self) -> usize {
-        self.inner.len()
+        match &self.inner {
+            Inner::Unordered(inner) => inner.len(),
+            Inner::Ordered { streams, .. } => streams.len(),
+        }
     }
 
     /// Returns `true` if the set contains no streams
     pub fn is_empty(&self) -> bool {
-        self.inner.is_empty()
+        match &self.inner {
+            Inner::Unordered(inner) => inner.is_empty(),
+            Inner::Ordered { streams, .. } => streams.is_empty(),
+        }
     }
 
     /// Push a stream into the set.
@@ -59,7 +115,13 @@ impl<St: Stream + Unpin> SelectAll<St> {
     /// ensure that `SelectAll::poll` is called in order to receive task
     /// notifications.
     pub fn push(&mut self, stream: St) {
-        self.inner.push(stream.into_future());
+        match &mut self.inner {
+            Inner::Unordered(inner) => inner.push(stream.into_future()),
+            Inner::Ordered { streams, is_terminated, .. } => {
+                streams.push(stream);
+                *is_terminated = false;
+            }
+        }
     }
 }
 
@@ -76,17 +138,28 @@ impl<St: Stream + Unpin> Stream for SelectAll<St> {
         mut self: Pin<&mut Self>,
         lw: &LocalWaker,
     ) -> Poll<Option<Self::Item>> {
-        match self.inner.poll_next_unpin(lw) {
+        let inner = match &mut self.inner {
+            Inner::Unordered(inner) => inner,
+            Inner::Ordered { streams, next, round_robin, is_terminated } => {
+                let res = poll_ordered(streams, next, *round_robin, lw);
+                if let Poll::Ready(None) = res {
+                    *is_terminated = true;
+                }
+                return res
+            }
+        };
+
+        match inner.poll_next_unpin(lw) {
             Poll::Pending => Poll::Pending,
             Poll::Ready(Some((Some(item), remaining))) => {
-                self.push(remaining);
+                inner.push(remaining.into_future());
                 Poll::Ready(Some(item))
             }
             Poll::Ready(Some((None, _))) => {
                 // FuturesUnordered thinks it isn't terminated
                 // because it yielded a Some. Here we poll it
                 // so it can realize it is terminated.
-                self.inner.poll_next_unpin(lw);
+                inner.poll_next_unpin(lw);
                 Poll::Ready(None)
             }
             Poll::Ready(_) => Poll::Ready(None),
@@ -94,9 +167,48 @@ impl<St: Stream + Unpin> Stream for SelectAll<St> {
     }
 }
 
+fn poll_ordered<St: Stream + Unpin>(
+    streams: &mut Vec<St>,
+    next: &mut usize,
+    round_robin: bool,
+    lw: &LocalWaker,
+) -> Poll<Option<St::Item>> {
+    let mut idx = if round_robin { *next } else { 0 };
+    let mut remaining = streams.len();
+
+    while remaining > 0 {
+        remaining -= 1;
+        if idx >= streams.len() {
+            idx = 0;
+        }
+
+        match streams[idx].poll_next_unpin(lw) {
+            Poll::Ready(Some(item)) => {
+                if round_robin {
+                    *next = idx + 1;
+                }
+                return Poll::Ready(Some(item));
+            }
+            // Removing the stream shifts its successors down, so `idx`
+            // already refers to the next stream to poll.
+            Poll::Ready(None) => { streams.remove(idx); }
+            Poll::Pending => idx += 1,
+        }
+    }
+
+    if streams.is_empty() {
+        Poll::Ready(None)
+    } else {
+        Poll::Pending
+    }
+}
+
 impl<St: Stream + Unpin> FusedStream for SelectAll<St> {
     fn is_terminated(&self) -> bool {
-        self.inner.is_terminated()
+        match &self.inner {
+            Inner::Unordered(inner) => inner.is_terminated(),
+            Inner::Ordered { is_terminated, .. } => *is_terminated,
+        }
     }
 }
 
@@ -121,3 +233,22 @@ pub fn select_all<I>(streams: I) -> SelectAll<I::Item>
 
     set
 }
+
+/// Convert a list of streams into a `Stream` of results from the streams,
+/// preferring earlier streams.
+///
+/// This is like [`select_all`], but whenever several of the streams have an
+/// item available the one that comes first in `streams` wins. See
+/// [`SelectAll::biased`] for details.
+pub fn select_all_biased<I>(streams: I) -> SelectAll<I::Item>
+    where I: IntoIterator,
+          I::Item: Stream + Unpin
+{
+    let mut set = SelectAll::biased();
+
+    for stream in streams {
+        set.push(stream);
+    }
+
+    set
+}
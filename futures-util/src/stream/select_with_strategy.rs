@@ -0,0 +1,238 @@
+use crate::stream::{StreamExt, Fuse};
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+
+/// Type to tell [`SelectWithStrategy`] which stream to poll next.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum PollNext {
+    /// Poll the first stream.
+    Left,
+    /// Poll the second stream.
+    Right,
+}
+
+impl PollNext {
+    /// Toggle the value and return the old one.
+    pub fn toggle(&mut self) -> Self {
+        let old = *self;
+
+        match self {
+            PollNext::Left => *self = PollNext::Right,
+            PollNext::Right => *self = PollNext::Left,
+        }
+
+        old
+    }
+}
+
+impl Default for PollNext {
+    fn default() -> PollNext {
+        PollNext::Left
+    }
+}
+
+/// Stream for the [`select_with_strategy`] function.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SelectWithStrategy<St1, St2, Clos, State> {
+    stream1: Fuse<St1>,
+    stream2: Fuse<St2>,
+    state: State,
+    clos: Clos,
+}
+
+impl<St1: Unpin, St2: Unpin, Clos, State> Unpin
+    for SelectWithStrategy<St1, St2, Clos, State> {}
+
+/// This function will attempt to pull items from both streams. You provide a
+/// closure to tell [`SelectWithStrategy`] which stream to poll. The closure
+/// can store state on `SelectWithStrategy` to which it will receive a
+/// `&mut` on every invocation. This allows basing the strategy on prior
+/// choices.
+///
+/// The closure is only consulted to pick which stream goes first; if the
+/// preferred stream isn't ready the other one is polled before returning
+/// `Poll::Pending`. After one of the two input streams completes, the
+/// remaining one will be polled exclusively. The returned stream completes
+/// when both input streams have completed.
+///
+/// Note that this function consumes both streams and returns a wrapped
+/// version of them.
+///
+/// # Examples
+///
+/// ## Priority
+///
+/// This example shows how to always prioritize the left stream, which is
+/// useful to let control messages preempt a stream of data:
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, PollNext, StreamExt, select_with_strategy};
+///
+/// let left = stream::iter(vec![1, 1, 1]);
+/// let right = stream::iter(vec![2, 2, 2]);
+///
+/// // We don't need any state, so let's make it an empty tuple.
+/// // We must provide some type here, as there is no way for the compiler
+/// // to infer it. As we don't need to capture variables, we can just
+/// // use a function pointer instead of a closure.
+/// fn prio_left(_: &mut ()) -> PollNext { PollNext::Left }
+///
+/// let out = select_with_strategy(left, right, prio_left);
+///
+/// assert_eq!(vec![1, 1, 1, 2, 2, 2], block_on(out.collect::<Vec<_>>()));
+/// ```
+///
+/// ## Round Robin
+///
+/// This example shows how to select from both streams round robin. Note that
+/// this is exactly what [`StreamExt::select`] does.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, PollNext, StreamExt, select_with_strategy};
+///
+/// let left = stream::iter(vec![1, 1, 1]);
+/// let right = stream::iter(vec![2, 2, 2]);
+///
+/// let rrobin = |last: &mut PollNext| last.toggle();
+///
+/// let out = select_with_strategy(left, right, rrobin);
+///
+/// assert_eq!(vec![1, 2, 1, 2, 1, 2], block_on(out.collect::<Vec<_>>()));
+/// ```
+///
+/// ## Weighted
+///
+/// This example gives the left stream two turns for every turn of the right
+/// stream:
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::stream::{self, PollNext, StreamExt, select_with_strategy};
+///
+/// let left = stream::iter(vec![1, 1, 1, 1]);
+/// let right = stream::iter(vec![2, 2]);
+///
+/// let weighted = |turn: &mut usize| {
+///     let next = if *turn % 3 == 2 { PollNext::Right } else { PollNext::Left };
+///     *turn += 1;
+///     next
+/// };
+///
+/// let out = select_with_strategy(left, right, weighted);
+///
+/// assert_eq!(vec![1, 1, 2, 1, 1, 2], block_on(out.collect::<Vec<_>>()));
+/// ```
+pub fn select_with_strategy<St1, St2, Clos, State>(
+    stream1: St1,
+    stream2: St2,
+    which_first: Clos,
+) -> SelectWithStrategy<St1, St2, Clos, State>
+    where St1: Stream,
+          St2: Stream<Item = St1::Item>,
+          Clos: FnMut(&mut State) -> PollNext,
+          State: Default,
+{
+    SelectWithStrategy {
+        stream1: stream1.fuse(),
+        stream2: stream2.fuse(),
+        state: Default::default(),
+        clos: which_first,
+    }
+}
+
+impl<St1, St2, Clos, State> SelectWithStrategy<St1, St2, Clos, State>
+    where St1: Stream,
+          St2: Stream,
+{
+    /// Acquires a reference to the underlying streams that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> (&St1, &St2) {
+        (self.stream1.get_ref(), self.stream2.get_ref())
+    }
+
+    /// Acquires a mutable reference to the underlying streams that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> (&mut St1, &mut St2) {
+        (self.stream1.get_mut(), self.stream2.get_mut())
+    }
+
+    /// Consumes this combinator, returning the underlying streams.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> (St1, St2) {
+        (self.stream1.into_inner(), self.stream2.into_inner())
+    }
+}
+
+impl<St1, St2, Clos, State> FusedStream for SelectWithStrategy<St1, St2, Clos, State> {
+    fn is_terminated(&self) -> bool {
+        self.stream1.is_terminated() && self.stream2.is_terminated()
+    }
+}
+
+impl<St1, St2, Clos, State> Stream for SelectWithStrategy<St1, St2, Clos, State>
+    where St1: Stream,
+          St2: Stream<Item = St1::Item>,
+          Clos: FnMut(&mut State) -> PollNext,
+{
+    type Item = St1::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St1::Item>> {
+        let which_first = {
+            let this = unsafe { Pin::get_unchecked_mut(self.as_mut()) };
+            (this.clos)(&mut this.state)
+        };
+
+        match which_first {
+            PollNext::Left => {
+                let (a, b) = self.as_mut().streams();
+                poll_inner(a, b, lw)
+            }
+            PollNext::Right => {
+                let (a, b) = self.as_mut().streams();
+                poll_inner(b, a, lw)
+            }
+        }
+    }
+}
+
+impl<St1, St2, Clos, State> SelectWithStrategy<St1, St2, Clos, State> {
+    #[allow(clippy::needless_lifetimes)] // https://github.com/rust-lang/rust/issues/52675
+    fn streams<'a>(self: Pin<&'a mut Self>) -> (Pin<&'a mut Fuse<St1>>, Pin<&'a mut Fuse<St2>>) {
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+        unsafe {
+            (Pin::new_unchecked(&mut this.stream1), Pin::new_unchecked(&mut this.stream2))
+        }
+    }
+}
+
+fn poll_inner<St1, St2>(
+    a: Pin<&mut Fuse<St1>>,
+    b: Pin<&mut Fuse<St2>>,
+    lw: &LocalWaker,
+) -> Poll<Option<St1::Item>>
+    where St1: Stream, St2: Stream<Item = St1::Item>
+{
+    let a_done = match a.poll_next(lw) {
+        Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+        Poll::Ready(None) => true,
+        Poll::Pending => false,
+    };
+
+    match b.poll_next(lw) {
+        Poll::Ready(Some(item)) => Poll::Ready(Some(item)),
+        Poll::Ready(None) if a_done => Poll::Ready(None),
+        Poll::Ready(None) | Poll::Pending => Poll::Pending,
+    }
+}
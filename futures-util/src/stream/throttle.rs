@@ -0,0 +1,85 @@
+use crate::future::FutureExt;
+use crate::stream::Fuse;
+use crate::timer::Clock;
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::time::Duration;
+
+/// Stream for the [`throttle`](super::StreamExt::throttle) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Throttle<St, C: Clock> {
+    stream: Fuse<St>,
+    clock: C,
+    period: Duration,
+    // Completes once the next item may be yielded.
+    delay: Option<C::Delay>,
+}
+
+impl<St: Unpin, C: Clock> Unpin for Throttle<St, C> {}
+
+impl<St: Stream, C: Clock> Throttle<St, C> {
+    unsafe_pinned!(stream: Fuse<St>);
+    unsafe_unpinned!(delay: Option<C::Delay>);
+
+    pub(super) fn new(stream: St, period: Duration, clock: C) -> Throttle<St, C> {
+        Throttle {
+            stream: super::Fuse::new(stream),
+            clock,
+            period,
+            delay: None,
+        }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        self.stream.get_ref()
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        self.stream.get_mut()
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream.into_inner()
+    }
+}
+
+impl<St: Stream, C: Clock> FusedStream for Throttle<St, C> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+impl<St: Stream, C: Clock> Stream for Throttle<St, C> {
+    type Item = St::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<St::Item>> {
+        if let Some(delay) = self.as_mut().delay() {
+            ready!(delay.poll_unpin(lw));
+            *self.as_mut().delay() = None;
+        }
+
+        let item = ready!(self.as_mut().stream().poll_next(lw));
+        if item.is_some() {
+            let delay = self.clock.delay_at(self.clock.now() + self.period);
+            *self.as_mut().delay() = Some(delay);
+        }
+        Poll::Ready(item)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::future::FutureExt;
+use crate::timer::{Clock, Elapsed};
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+use std::time::Duration;
+
+/// Stream for the [`timeout`](super::StreamExt::timeout) method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Timeout<St, C: Clock> {
+    stream: St,
+    clock: C,
+    dur: Duration,
+    // Deadline for the next item, started on the first poll after an item
+    // has been yielded.
+    delay: Option<C::Delay>,
+}
+
+impl<St: Unpin, C: Clock> Unpin for Timeout<St, C> {}
+
+impl<St: Stream, C: Clock> Timeout<St, C> {
+    unsafe_pinned!(stream: St);
+    unsafe_unpinned!(delay: Option<C::Delay>);
+
+    pub(super) fn new(stream: St, dur: Duration, clock: C) -> Timeout<St, C> {
+        Timeout { stream, clock, dur, delay: None }
+    }
+
+    /// Acquires a reference to the underlying stream that this combinator is
+    /// pulling from.
+    pub fn get_ref(&self) -> &St {
+        &self.stream
+    }
+
+    /// Acquires a mutable reference to the underlying stream that this
+    /// combinator is pulling from.
+    ///
+    /// Note that care must be taken to avoid tampering with the state of the
+    /// stream which may otherwise confuse this combinator.
+    pub fn get_mut(&mut self) -> &mut St {
+        &mut self.stream
+    }
+
+    /// Consumes this combinator, returning the underlying stream.
+    ///
+    /// Note that this may discard intermediate state of this combinator, so
+    /// care should be taken to avoid losing resources when this is called.
+    pub fn into_inner(self) -> St {
+        self.stream
+    }
+}
+
+impl<St: FusedStream, C: Clock> FusedStream for Timeout<St, C> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+impl<St: Stream, C: Clock> Stream for Timeout<St, C> {
+    type Item = Result<St::Item, Elapsed>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<Self::Item>> {
+        if let Poll::Ready(item) = self.as_mut().stream().poll_next(lw) {
+            *self.as_mut().delay() = None;
+            return Poll::Ready(item.map(Ok));
+        }
+
+        if self.delay.is_none() {
+            let delay = self.clock.delay_at(self.clock.now() + self.dur);
+            *self.as_mut().delay() = Some(delay);
+        }
+
+        ready!(self.as_mut().delay().as_mut().unwrap().poll_unpin(lw));
+        *self.as_mut().delay() = None;
+        Poll::Ready(Some(Err(Elapsed)))
+    }
+}
@@ -0,0 +1,66 @@
+use core::mem;
+use core::pin::Pin;
+use futures_core::future::{FusedFuture, Future};
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+
+/// A future which collects all of the pairs of a stream into two collections.
+///
+/// This future is created by the `Stream::unzip` method.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct Unzip<St, FromA, FromB> {
+    stream: St,
+    left: FromA,
+    right: FromB,
+}
+
+impl<St: Unpin + Stream, FromA, FromB> Unpin for Unzip<St, FromA, FromB> {}
+
+impl<St: Stream, FromA: Default, FromB: Default> Unzip<St, FromA, FromB> {
+    unsafe_pinned!(stream: St);
+    unsafe_unpinned!(left: FromA);
+    unsafe_unpinned!(right: FromB);
+
+    fn finish(mut self: Pin<&mut Self>) -> (FromA, FromB) {
+        (
+            mem::replace(self.as_mut().left(), Default::default()),
+            mem::replace(self.as_mut().right(), Default::default()),
+        )
+    }
+
+    pub(super) fn new(stream: St) -> Unzip<St, FromA, FromB> {
+        Unzip {
+            stream,
+            left: Default::default(),
+            right: Default::default(),
+        }
+    }
+}
+
+impl<St: FusedStream, FromA, FromB> FusedFuture for Unzip<St, FromA, FromB> {
+    fn is_terminated(&self) -> bool {
+        self.stream.is_terminated()
+    }
+}
+
+impl<St, A, B, FromA, FromB> Future for Unzip<St, FromA, FromB>
+where St: Stream<Item = (A, B)>,
+      FromA: Default + Extend<A>,
+      FromB: Default + Extend<B>,
+{
+    type Output = (FromA, FromB);
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<(FromA, FromB)> {
+        loop {
+            match ready!(self.as_mut().stream().poll_next(lw)) {
+                Some((a, b)) => {
+                    self.as_mut().left().extend(Some(a));
+                    self.as_mut().right().extend(Some(b));
+                }
+                None => return Poll::Ready(self.as_mut().finish()),
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use crate::stream::{StreamExt, Fuse};
+use core::pin::Pin;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use pin_utils::{unsafe_pinned, unsafe_unpinned};
+
+/// A value yielded by [`ZipLongest`], holding an item from either or both of
+/// the zipped streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EitherOrBoth<A, B> {
+    /// Both streams yielded an item.
+    Both(A, B),
+    /// Only the first stream yielded an item, the second one has completed.
+    Left(A),
+    /// Only the second stream yielded an item, the first one has completed.
+    Right(B),
+}
+
+impl<A, B> EitherOrBoth<A, B> {
+    /// Returns the item of the first stream, if present.
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherOrBoth::Both(a, _) | EitherOrBoth::Left(a) => Some(a),
+            EitherOrBoth::Right(_) => None,
+        }
+    }
+
+    /// Returns the item of the second stream, if present.
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherOrBoth::Both(_, b) | EitherOrBoth::Right(b) => Some(b),
+            EitherOrBoth::Left(_) => None,
+        }
+    }
+}
+
+/// An adapter for zipping two streams together until both have completed.
+///
+/// This combinator is created by the `Stream::zip_longest` method.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ZipLongest<St1: Stream, St2: Stream> {
+    stream1: Fuse<St1>,
+    stream2: Fuse<St2>,
+    queued1: Option<St1::Item>,
+    queued2: Option<St2::Item>,
+}
+
+impl<St1: Stream + Unpin, St2: Stream + Unpin> Unpin for ZipLongest<St1, St2> {}
+
+impl<St1: Stream, St2: Stream> ZipLongest<St1, St2> {
+    unsafe_pinned!(stream1: Fuse<St1>);
+    unsafe_pinned!(stream2: Fuse<St2>);
+    unsafe_unpinned!(queued1: Option<St1::Item>);
+    unsafe_unpinned!(queued2: Option<St2::Item>);
+
+    pub(super) fn new(stream1: St1, stream2: St2) -> ZipLongest<St1, St2> {
+        ZipLongest {
+            stream1: stream1.fuse(),
+            stream2: stream2.fuse(),
+            queued1: None,
+            queued2: None,
+        }
+    }
+}
+
+impl<St1, St2> FusedStream for ZipLongest<St1, St2>
+    where St1: Stream, St2: Stream,
+{
+    fn is_terminated(&self) -> bool {
+        self.queued1.is_none() && self.queued2.is_none() &&
+            self.stream1.is_terminated() && self.stream2.is_terminated()
+    }
+}
+
+impl<St1, St2> Stream for ZipLongest<St1, St2>
+    where St1: Stream, St2: Stream
+{
+    type Item = EitherOrBoth<St1::Item, St2::Item>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker
+    ) -> Poll<Option<Self::Item>> {
+        if self.queued1.is_none() {
+            match self.as_mut().stream1().poll_next(lw) {
+                Poll::Ready(Some(item1)) => *self.as_mut().queued1() = Some(item1),
+                Poll::Ready(None) | Poll::Pending => {}
+            }
+        }
+        if self.queued2.is_none() {
+            match self.as_mut().stream2().poll_next(lw) {
+                Poll::Ready(Some(item2)) => *self.as_mut().queued2() = Some(item2),
+                Poll::Ready(None) | Poll::Pending => {}
+            }
+        }
+
+        let done1 = self.stream1.is_done();
+        let done2 = self.stream2.is_done();
+
+        match (self.queued1.is_some(), self.queued2.is_some()) {
+            (true, true) => {
+                let item1 = self.as_mut().queued1().take().unwrap();
+                let item2 = self.as_mut().queued2().take().unwrap();
+                Poll::Ready(Some(EitherOrBoth::Both(item1, item2)))
+            }
+            (true, false) if done2 => {
+                let item1 = self.as_mut().queued1().take().unwrap();
+                Poll::Ready(Some(EitherOrBoth::Left(item1)))
+            }
+            (false, true) if done1 => {
+                let item2 = self.as_mut().queued2().take().unwrap();
+                Poll::Ready(Some(EitherOrBoth::Right(item2)))
+            }
+            (false, false) if done1 && done2 => Poll::Ready(None),
+            _ => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use super::{Delay, TimerHandle};
+use futures_core::future::Future;
+use std::error::Error;
+use std::fmt;
+use std::time::Instant;
+
+/// A source of time used by the timeout combinators such as
+/// [`FutureExt::timeout`](crate::future::FutureExt::timeout).
+///
+/// This trait is implemented for [`TimerHandle`], so the combinators can be
+/// driven either by a timer following the system clock or by a mock clock.
+/// Other timer implementations can be plugged in by implementing it.
+pub trait Clock {
+    /// The future returned by [`delay_at`](Clock::delay_at).
+    type Delay: Future<Output = ()> + Unpin;
+
+    /// Returns the current time according to this clock.
+    fn now(&self) -> Instant;
+
+    /// Creates a future which completes once this clock reaches `at`.
+    fn delay_at(&self, at: Instant) -> Self::Delay;
+}
+
+impl Clock for TimerHandle {
+    type Delay = Delay;
+
+    fn now(&self) -> Instant {
+        TimerHandle::now(self)
+    }
+
+    fn delay_at(&self, at: Instant) -> Delay {
+        TimerHandle::delay_at(self, at)
+    }
+}
+
+/// Error returned by the timeout combinators when their deadline has
+/// elapsed.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "deadline has elapsed")
+    }
+}
+
+impl Error for Elapsed {}
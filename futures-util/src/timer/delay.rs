@@ -0,0 +1,94 @@
+use super::driver::{Entry, TimerHandle};
+use futures_core::future::{FusedFuture, Future};
+use futures_core::task::{LocalWaker, Poll};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A future which completes at a specific point in time.
+///
+/// Delays created with [`Delay::new`] or [`Delay::new_at`] use the global
+/// timer, other timers can be used through [`TimerHandle::delay`].
+#[must_use = "futures do nothing unless polled"]
+pub struct Delay {
+    handle: TimerHandle,
+    entry: Arc<Entry>,
+    when: Instant,
+}
+
+impl Delay {
+    /// Creates a new future which completes once `dur` has elapsed.
+    ///
+    /// Example:
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::timer::Delay;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// block_on(Delay::new(Duration::from_millis(10)));
+    /// assert!(start.elapsed() >= Duration::from_millis(10));
+    /// ```
+    pub fn new(dur: Duration) -> Delay {
+        TimerHandle::default().delay(dur)
+    }
+
+    /// Creates a new future which completes at `at`.
+    pub fn new_at(at: Instant) -> Delay {
+        TimerHandle::default().delay_at(at)
+    }
+
+    pub(super) fn new_handle(at: Instant, handle: TimerHandle) -> Delay {
+        let entry = handle.inner.register(at);
+        Delay { handle, entry, when: at }
+    }
+
+    /// Returns the instant at which this future completes.
+    pub fn deadline(&self) -> Instant {
+        self.when
+    }
+
+    /// Resets this delay to complete at `at` instead, regardless of whether
+    /// it has already completed.
+    pub fn reset(&mut self, at: Instant) {
+        self.handle.inner.reset(&self.entry, self.when, at);
+        self.when = at;
+    }
+
+    pub(super) fn handle(&self) -> &TimerHandle {
+        &self.handle
+    }
+}
+
+impl fmt::Debug for Delay {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Delay")
+            .field("when", &self.when)
+            .finish()
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<()> {
+        if self.entry.is_fired() {
+            return Poll::Ready(());
+        }
+
+        self.entry.waker.register(lw);
+        if self.entry.is_fired() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl FusedFuture for Delay {
+    fn is_terminated(&self) -> bool {
+        self.entry.is_fired()
+    }
+}
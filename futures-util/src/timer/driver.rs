@@ -0,0 +1,370 @@
+use super::wheel::Wheel;
+use super::{Delay, Interval};
+use crate::task::AtomicWaker;
+use lazy_static::lazy_static;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A timer which keeps track of pending [`Delay`]s and wakes them once their
+/// deadline has been reached.
+///
+/// A `Timer` has to be driven for its delays to complete, which can happen
+/// in one of two ways:
+///
+/// - A timer created with [`Timer::new`] follows the system clock and is
+///   driven by a thread, either by calling [`run`](Timer::run) on a thread
+///   of your own or by handing it to a background thread with
+///   [`spawn`](Timer::spawn).
+/// - A timer created with [`Timer::new_mock`] follows a mock clock which
+///   only moves forward when [`advance`](Timer::advance) is called, which
+///   makes code using timeouts deterministically testable.
+///
+/// Delays and intervals are created through a [`TimerHandle`]. Timers have a
+/// resolution of one millisecond, and a delay never completes before its
+/// deadline.
+pub struct Timer {
+    inner: Arc<Inner>,
+}
+
+/// A handle to a [`Timer`], used to create [`Delay`]s and [`Interval`]s.
+///
+/// The default handle refers to a global timer which is driven by a
+/// background thread that is spawned the first time it is used.
+pub struct TimerHandle {
+    pub(super) inner: Arc<Inner>,
+}
+
+pub(super) struct Inner {
+    start: Instant,
+    state: Mutex<State>,
+    // Notified whenever the driver thread may have to wake up earlier.
+    condvar: Condvar,
+    // Number of live `TimerHandle`s, `run` returns once it drops to zero.
+    handles: AtomicUsize,
+}
+
+struct State {
+    // Entries along with the generation they were inserted for.
+    wheel: Wheel<(Weak<Entry>, usize)>,
+    // The offset from `start` of a mock clock, `None` for the system clock.
+    mock_now: Option<Duration>,
+}
+
+pub(super) struct Entry {
+    pub(super) waker: AtomicWaker,
+    // The generation of the entry with the `FIRED` bit set once it has
+    // fired. Resetting a delay starts a new generation, so that the driver
+    // can't fire it for a deadline it no longer has.
+    state: AtomicUsize,
+}
+
+const FIRED: usize = 1;
+
+impl Timer {
+    /// Creates a new timer which follows the system clock.
+    ///
+    /// The timer needs to be driven by [`run`](Timer::run) or
+    /// [`spawn`](Timer::spawn), otherwise its delays never complete.
+    pub fn new() -> Timer {
+        Timer::with_clock(None)
+    }
+
+    /// Creates a new timer which follows a mock clock.
+    ///
+    /// The clock starts at the current time and stands still until it is
+    /// moved forward with [`advance`](Timer::advance).
+    pub fn new_mock() -> Timer {
+        Timer::with_clock(Some(Duration::from_secs(0)))
+    }
+
+    fn with_clock(mock_now: Option<Duration>) -> Timer {
+        Timer {
+            inner: Arc::new(Inner {
+                start: Instant::now(),
+                state: Mutex::new(State {
+                    wheel: Wheel::new(),
+                    mock_now,
+                }),
+                condvar: Condvar::new(),
+                handles: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Returns a handle to this timer.
+    pub fn handle(&self) -> TimerHandle {
+        TimerHandle::new(self.inner.clone())
+    }
+
+    /// Returns the current time according to this timer's clock.
+    pub fn now(&self) -> Instant {
+        self.inner.now()
+    }
+
+    /// Moves the mock clock forward by `dur`, completing every delay whose
+    /// deadline has been reached.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the timer wasn't created with
+    /// [`Timer::new_mock`].
+    pub fn advance(&self, dur: Duration) {
+        let expired = {
+            let mut state = self.inner.state.lock().unwrap();
+            match &mut state.mock_now {
+                Some(now) => *now += dur,
+                None => panic!("`advance` called on a timer without a mock clock"),
+            }
+            self.inner.expire(&mut state)
+        };
+        fire(expired);
+    }
+
+    /// Drives this timer on the current thread, blocking until every
+    /// [`TimerHandle`] to it has been dropped.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the timer was created with
+    /// [`Timer::new_mock`].
+    pub fn run(self) {
+        let inner = &self.inner;
+        let mut state = inner.state.lock().unwrap();
+        assert!(state.mock_now.is_none(), "`run` called on a timer with a mock clock");
+
+        loop {
+            let expired = inner.expire(&mut state);
+            if !expired.is_empty() {
+                drop(state);
+                fire(expired);
+                state = inner.state.lock().unwrap();
+                continue;
+            }
+
+            if inner.handles.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+
+            state = match state.wheel.next_expiration() {
+                Some(tick) => {
+                    let deadline = inner.start + Duration::from_millis(tick);
+                    let now = Instant::now();
+                    if deadline <= now {
+                        continue;
+                    }
+                    inner.condvar.wait_timeout(state, deadline - now).unwrap().0
+                }
+                None => inner.condvar.wait(state).unwrap(),
+            };
+        }
+    }
+
+    /// Spawns a background thread which drives this timer and returns a
+    /// handle to it.
+    ///
+    /// The thread exits once every handle to the timer has been dropped.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the timer was created with
+    /// [`Timer::new_mock`] or if the thread can't be spawned.
+    pub fn spawn(self) -> TimerHandle {
+        let handle = self.handle();
+        thread::Builder::new()
+            .name("futures-timer".to_string())
+            .spawn(move || self.run())
+            .expect("failed to spawn timer thread");
+        handle
+    }
+}
+
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new()
+    }
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Timer").finish()
+    }
+}
+
+impl TimerHandle {
+    fn new(inner: Arc<Inner>) -> TimerHandle {
+        inner.handles.fetch_add(1, Ordering::SeqCst);
+        TimerHandle { inner }
+    }
+
+    /// Returns the current time according to the timer's clock.
+    pub fn now(&self) -> Instant {
+        self.inner.now()
+    }
+
+    /// Creates a future which completes once `dur` has elapsed.
+    pub fn delay(&self, dur: Duration) -> Delay {
+        self.delay_at(self.now() + dur)
+    }
+
+    /// Creates a future which completes once the timer's clock reaches
+    /// `at`.
+    pub fn delay_at(&self, at: Instant) -> Delay {
+        Delay::new_handle(at, self.clone())
+    }
+
+    /// Creates a stream which yields every `period`, starting one `period`
+    /// from now.
+    pub fn interval(&self, period: Duration) -> Interval {
+        self.interval_at(self.now() + period, period)
+    }
+
+    /// Creates a stream which yields at `start` and then every `period`.
+    pub fn interval_at(&self, start: Instant, period: Duration) -> Interval {
+        Interval::new_handle(start, period, self.clone())
+    }
+}
+
+lazy_static! {
+    static ref GLOBAL_TIMER: TimerHandle = Timer::new().spawn();
+}
+
+impl Default for TimerHandle {
+    fn default() -> TimerHandle {
+        GLOBAL_TIMER.clone()
+    }
+}
+
+impl Clone for TimerHandle {
+    fn clone(&self) -> TimerHandle {
+        TimerHandle::new(self.inner.clone())
+    }
+}
+
+impl Drop for TimerHandle {
+    fn drop(&mut self) {
+        if self.inner.handles.fetch_sub(1, Ordering::SeqCst) == 1 {
+            // Take the lock so the driver can't miss the notification
+            // between checking the count and going to sleep.
+            let _state = self.inner.state.lock();
+            self.inner.condvar.notify_all();
+        }
+    }
+}
+
+impl fmt::Debug for TimerHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TimerHandle").finish()
+    }
+}
+
+impl Inner {
+    fn now(&self) -> Instant {
+        match self.state.lock().unwrap().mock_now {
+            Some(offset) => self.start + offset,
+            None => Instant::now(),
+        }
+    }
+
+    fn since_start(&self, at: Instant) -> Duration {
+        if at > self.start {
+            at - self.start
+        } else {
+            Duration::from_secs(0)
+        }
+    }
+
+    // The number of whole ticks that have passed on the clock.
+    fn now_tick(&self, state: &State) -> u64 {
+        let elapsed = match state.mock_now {
+            Some(offset) => offset,
+            None => self.since_start(Instant::now()),
+        };
+        elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_millis())
+    }
+
+    // The tick at which an entry for `at` fires, rounded up so that it never
+    // fires early.
+    fn tick_for(&self, at: Instant) -> u64 {
+        let offset = self.since_start(at);
+        let tick = offset.as_secs() * 1000 + u64::from(offset.subsec_millis());
+        if offset.subsec_nanos() % 1_000_000 != 0 {
+            tick + 1
+        } else {
+            tick
+        }
+    }
+
+    /// Registers a new entry which is fired once `at` has been reached.
+    pub(super) fn register(&self, at: Instant) -> Arc<Entry> {
+        let entry = Arc::new(Entry {
+            waker: AtomicWaker::new(),
+            state: AtomicUsize::new(0),
+        });
+        self.schedule(self.state.lock().unwrap(), &entry, at);
+        entry
+    }
+
+    /// Moves `entry` from its deadline `old` to `at`, clearing its fired flag.
+    pub(super) fn reset(&self, entry: &Arc<Entry>, old: Instant, at: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.wheel.remove(self.tick_for(old), |(weak, _)| {
+            weak.upgrade().map_or(false, |other| Arc::ptr_eq(&other, entry))
+        });
+        let generation = entry.state.load(Ordering::SeqCst) & !FIRED;
+        entry.state.store(generation.wrapping_add(FIRED + 1), Ordering::SeqCst);
+        self.schedule(state, entry, at);
+    }
+
+    // Inserts `entry` into the wheel, only waking the driver if it has to
+    // wake up earlier than it planned to.
+    fn schedule(&self, mut state: MutexGuard<'_, State>, entry: &Arc<Entry>, at: Instant) {
+        let generation = entry.state.load(Ordering::SeqCst);
+        let tick = self.tick_for(at);
+        let now = self.now_tick(&state);
+        let next = state.wheel.next_expiration();
+        let inserted = tick > now
+            && state.wheel.insert(tick, (Arc::downgrade(entry), generation)).is_ok();
+        let earlier = state.wheel.next_expiration() != next;
+        drop(state);
+
+        if !inserted {
+            entry.state.store(generation | FIRED, Ordering::SeqCst);
+        } else if earlier {
+            self.condvar.notify_all();
+        }
+    }
+
+    fn expire(&self, state: &mut MutexGuard<'_, State>) -> Vec<(Weak<Entry>, usize)> {
+        let mut expired = Vec::new();
+        let now = self.now_tick(state);
+        state.wheel.advance(now, &mut expired);
+        expired
+    }
+}
+
+impl Entry {
+    pub(super) fn is_fired(&self) -> bool {
+        self.state.load(Ordering::SeqCst) & FIRED != 0
+    }
+
+    // Fires the entry unless it has been reset since it was inserted for
+    // `generation`.
+    fn fire(&self, generation: usize) {
+        let fired = generation | FIRED;
+        if self.state.compare_and_swap(generation, fired, Ordering::SeqCst) == generation {
+            self.waker.wake();
+        }
+    }
+}
+
+fn fire(expired: Vec<(Weak<Entry>, usize)>) {
+    for (entry, generation) in expired {
+        // Entries whose delay has been dropped don't need to be fired.
+        if let Some(entry) = entry.upgrade() {
+            entry.fire(generation);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use super::{Delay, TimerHandle};
+use crate::future::FutureExt;
+use futures_core::stream::{FusedStream, Stream};
+use futures_core::task::{LocalWaker, Poll};
+use std::cmp;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use std::u32;
+
+/// A stream which yields `()` at a fixed period.
+///
+/// If the stream isn't polled for longer than a period, the missed ticks are
+/// skipped rather than delivered in a burst, so the stream keeps yielding on
+/// multiples of the period from its start.
+///
+/// Intervals created with [`Interval::new`] or [`Interval::new_at`] use the
+/// global timer, other timers can be used through
+/// [`TimerHandle::interval`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Interval {
+    delay: Delay,
+    period: Duration,
+}
+
+impl Interval {
+    /// Creates a new stream which yields every `period`, starting one
+    /// `period` from now.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    pub fn new(period: Duration) -> Interval {
+        TimerHandle::default().interval(period)
+    }
+
+    /// Creates a new stream which yields at `start` and then every
+    /// `period`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `period` is zero.
+    pub fn new_at(start: Instant, period: Duration) -> Interval {
+        TimerHandle::default().interval_at(start, period)
+    }
+
+    pub(super) fn new_handle(
+        start: Instant,
+        period: Duration,
+        handle: TimerHandle,
+    ) -> Interval {
+        assert!(period > Duration::from_secs(0), "interval period must be non-zero");
+        Interval {
+            delay: handle.delay_at(start),
+            period,
+        }
+    }
+
+    /// Returns the period of this interval.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+}
+
+impl Stream for Interval {
+    type Item = ();
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        lw: &LocalWaker,
+    ) -> Poll<Option<()>> {
+        ready!(self.delay.poll_unpin(lw));
+
+        let now = self.delay.handle().now();
+        let next = next_tick(self.delay.deadline(), now, self.period);
+        self.delay.reset(next);
+        Poll::Ready(Some(()))
+    }
+}
+
+impl FusedStream for Interval {
+    fn is_terminated(&self) -> bool {
+        false
+    }
+}
+
+// Returns the first multiple of `period` after `prev` which lies in the
+// future.
+fn next_tick(prev: Instant, now: Instant, period: Duration) -> Instant {
+    let next = prev + period;
+    if next > now {
+        return next;
+    }
+
+    // `Duration` can only be multiplied by a `u32`, so skip ahead in steps
+    // in case we fell behind by more periods than that.
+    let behind = now - prev;
+    let mut periods = behind.as_nanos() / period.as_nanos() + 1;
+    let mut next = prev;
+    while periods > 0 {
+        let step = cmp::min(periods, u128::from(u32::MAX));
+        next += period * step as u32;
+        periods -= step;
+    }
+    next
+}
@@ -0,0 +1,22 @@
+//! Timers driven independently of any executor.
+//!
+//! This module contains [`Delay`], a future which completes at a point in
+//! time, and [`Interval`], a stream which yields at a fixed period. Both are
+//! backed by a [`Timer`], a hierarchical timer wheel which is driven either
+//! by a thread following the system clock or manually through a mock clock.
+//! Since timers don't rely on the executor, they can be used with any
+//! executor, such as `LocalPool` or `ThreadPool`.
+
+mod clock;
+pub use self::clock::{Clock, Elapsed};
+
+mod delay;
+pub use self::delay::Delay;
+
+mod driver;
+pub use self::driver::{Timer, TimerHandle};
+
+mod interval;
+pub use self::interval::Interval;
+
+mod wheel;
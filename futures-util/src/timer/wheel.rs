@@ -0,0 +1,158 @@
+//! A hierarchical hashed timer wheel.
+//!
+//! Time is measured in ticks since the wheel was created. Level `n` of the
+//! wheel has 64 slots which each cover `64^n` ticks, so an entry is stored in
+//! the lowest level whose slot boundaries separate its deadline from the
+//! current time. As time advances entries cascade down to lower levels until
+//! they expire from level 0.
+
+use std::cmp;
+use std::fmt;
+use std::mem;
+
+const SLOT_BITS: usize = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 6;
+
+pub(super) struct Wheel<T> {
+    // The current tick. No entry expires at or before it.
+    elapsed: u64,
+    levels: Vec<Level<T>>,
+    len: usize,
+}
+
+struct Level<T> {
+    // Bit `n` is set if `slots[n]` is non-empty.
+    occupied: u64,
+    slots: Vec<Vec<(u64, T)>>,
+}
+
+impl<T> fmt::Debug for Wheel<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Wheel")
+            .field("elapsed", &self.elapsed)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl<T> Wheel<T> {
+    pub(super) fn new() -> Wheel<T> {
+        Wheel {
+            elapsed: 0,
+            levels: (0..LEVELS).map(|_| Level {
+                occupied: 0,
+                slots: (0..SLOTS).map(|_| Vec::new()).collect(),
+            }).collect(),
+            len: 0,
+        }
+    }
+
+    /// Inserts `item` to expire at tick `when`.
+    ///
+    /// Returns the item back if `when` has already been reached.
+    pub(super) fn insert(&mut self, when: u64, item: T) -> Result<(), T> {
+        if when <= self.elapsed {
+            return Err(item);
+        }
+
+        self.len += 1;
+        self.place(when, item);
+        Ok(())
+    }
+
+    /// Removes the item expiring at tick `when` for which `matches` returns
+    /// `true`, if it hasn't expired yet.
+    pub(super) fn remove<F>(&mut self, when: u64, mut matches: F) -> Option<T>
+        where F: FnMut(&T) -> bool,
+    {
+        // Items cascade down as time advances, so the item may be in the slot
+        // covering `when` on any level.
+        for (index, level) in self.levels.iter_mut().enumerate() {
+            let slot = ((when >> (index * SLOT_BITS)) as usize) & (SLOTS - 1);
+            let entries = &mut level.slots[slot];
+            let position = entries.iter()
+                .position(|(item_when, item)| *item_when == when && matches(item));
+            if let Some(position) = position {
+                let (_, item) = entries.swap_remove(position);
+                if entries.is_empty() {
+                    level.occupied &= !(1 << slot);
+                }
+                self.len -= 1;
+                return Some(item);
+            }
+        }
+        None
+    }
+
+    fn place(&mut self, when: u64, item: T) {
+        let level = level_for(self.elapsed, when);
+        let slot = ((when >> (level * SLOT_BITS)) as usize) & (SLOTS - 1);
+        let level = &mut self.levels[level];
+        level.slots[slot].push((when, item));
+        level.occupied |= 1 << slot;
+    }
+
+    /// Returns the tick at which the next slot has to be processed, which is
+    /// at or before the tick the earliest entry expires.
+    pub(super) fn next_expiration(&self) -> Option<u64> {
+        self.next_slot().map(|(_, _, deadline)| deadline)
+    }
+
+    /// Advances the wheel to tick `now`, moving every entry which expires at
+    /// or before it to `expired`.
+    pub(super) fn advance(&mut self, now: u64, expired: &mut Vec<T>) {
+        while let Some((level, slot, deadline)) = self.next_slot() {
+            if deadline > now {
+                break;
+            }
+
+            self.elapsed = deadline;
+            let level = &mut self.levels[level];
+            level.occupied &= !(1 << slot);
+            let entries = mem::replace(&mut level.slots[slot], Vec::new());
+            for (when, item) in entries {
+                if when <= self.elapsed {
+                    self.len -= 1;
+                    expired.push(item);
+                } else {
+                    // Cascade down to a lower level.
+                    self.place(when, item);
+                }
+            }
+        }
+
+        self.elapsed = cmp::max(self.elapsed, now);
+    }
+
+    fn next_slot(&self) -> Option<(usize, usize, u64)> {
+        // An entry in a lower level always expires before any entry in a
+        // higher level, so the first occupied level contains the next slot.
+        let (level, occupied) = self.levels.iter()
+            .map(|level| level.occupied)
+            .enumerate()
+            .find(|&(_, occupied)| occupied != 0)?;
+
+        let slot_range = 1u64 << (level * SLOT_BITS);
+        let level_range = slot_range << SLOT_BITS;
+        let now_slot = ((self.elapsed / slot_range) as usize) & (SLOTS - 1);
+        let zeros = occupied.rotate_right(now_slot as u32).trailing_zeros() as usize;
+        let slot = (now_slot + zeros) & (SLOTS - 1);
+
+        let level_start = self.elapsed & !(level_range - 1);
+        let mut deadline = level_start + slot as u64 * slot_range;
+        if level > 0 && deadline <= self.elapsed {
+            // Entries too far in the future for the top level wrap around,
+            // so their slot lies in the next rotation of the level.
+            deadline += level_range;
+        }
+
+        Some((level, slot, deadline))
+    }
+}
+
+fn level_for(elapsed: u64, when: u64) -> usize {
+    let masked = (elapsed ^ when) | (SLOTS as u64 - 1);
+    let significant = 63 - masked.leading_zeros() as usize;
+    cmp::min(significant / SLOT_BITS, LEVELS - 1)
+}
@@ -8,24 +8,6 @@ use futures_sink::Sink;
 
 #[cfg(feature = "compat")] use crate::compat::Compat;
 
-/* TODO
-mod join;
-mod select;
-pub use self::join::{Join, Join3, Join4, Join5};
-pub use self::select::Select;
-
-#[cfg(feature = "std")]
-mod join_all;
-#[cfg(feature = "std")]
-mod select_all;
-#[cfg(feature = "std")]
-mod select_ok;
-#[cfg(feature = "std")]
-pub use self::select_all::{SelectAll, SelectAllNext, select_all};
-#[cfg(feature = "std")]
-pub use self::select_ok::{SelectOk, select_ok};
-*/
-
 // Combinators
 mod and_then;
 pub use self::and_then::AndThen;
@@ -39,6 +21,9 @@ pub use self::flatten_sink::FlattenSink;
 mod try_join;
 pub use self::try_join::{TryJoin, TryJoin3, TryJoin4, TryJoin5};
 
+mod try_select;
+pub use self::try_select::{try_select, try_select_biased, TrySelect};
+
 mod into_future;
 pub use self::into_future::IntoFuture;
 
@@ -58,7 +43,20 @@ pub use self::unwrap_or_else::UnwrapOrElse;
 mod try_join_all;
 
 #[cfg(feature = "std")]
-pub use self::try_join_all::{try_join_all, TryJoinAll};
+pub use self::try_join_all::{try_join_all, try_join_all_limited, TryJoinAll};
+
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+pub use self::retry::{
+    retry, retry_with_clock, Retry, RetryPolicy, RetryIf, ConstantBackoff,
+    ExponentialBackoff,
+};
+
+#[cfg(feature = "std")]
+mod select_ok;
+#[cfg(feature = "std")]
+pub use self::select_ok::{select_ok, SelectOk};
 
 // Implementation details
 mod try_chain;
@@ -325,49 +323,37 @@ pub trait TryFutureExt: TryFuture {
         OrElse::new(self, f)
     }
 
-    /* TODO
-    /// Waits for either one of two differently-typed futures to complete.
+    /// Waits for either this or the `other` future to complete.
     ///
-    /// This function will return a new future which awaits for either this or
-    /// the `other` future to complete. The returned future will finish with
-    /// both the value resolved and a future representing the completion of the
-    /// other work.
+    /// This method returns a new future which resolves with the first
+    /// success or the first error of either future, together with the other
+    /// future, which hasn't completed yet. See [`try_select()`] for details.
     ///
-    /// Note that this function consumes the receiving futures and returns a
+    /// Note that this method consumes the receiving futures and returns a
     /// wrapped version of them.
     ///
-    /// Also note that if both this and the second future have the same
-    /// success/error type you can use the `Either::split` method to
-    /// conveniently extract out the value at the end.
-    ///
     /// # Examples
     ///
     /// ```
-    /// use futures::future::{self, Either};
+    /// use futures::executor::block_on;
+    /// use futures::future::{self, Either, TryFutureExt};
     ///
-    /// // A poor-man's join implemented on top of select
+    /// let a = future::ok::<i32, i32>(1);
+    /// let b = future::empty::<Result<i32, i32>>();
     ///
-    /// fn join<A, B, E>(a: A, b: B) -> Box<Future<Item=(A::Item, B::Item), Error=E>>
-    ///     where A: Future<Error = E> + 'static,
-    ///           B: Future<Error = E> + 'static,
-    ///           E: 'static,
-    /// {
-    ///     Box::new(a.select(b).then(|res| -> Box<Future<Item=_, Error=_>> {
-    ///         match res {
-    ///             Ok(Either::Left((x, b))) => Box::new(b.map(move |y| (x, y))),
-    ///             Ok(Either::Right((y, a))) => Box::new(a.map(move |x| (x, y))),
-    ///             Err(Either::Left((e, _))) => Box::new(future::err(e)),
-    ///             Err(Either::Right((e, _))) => Box::new(future::err(e)),
-    ///         }
-    ///     }))
-    /// }}
+    /// match block_on(a.select(b)) {
+    ///     Ok(Either::Left((value, _b))) => assert_eq!(value, 1),
+    ///     _ => unreachable!(),
+    /// }
     /// ```
-    fn select<B>(self, other: B) -> Select<Self, B::Future>
-        where B: IntoFuture, Self: Sized
+    fn select<Fut>(self, other: Fut) -> TrySelect<Self, Fut>
+        where Fut: TryFuture + Unpin,
+              Self: Unpin + Sized,
     {
-        select::new(self, other.into_future())
+        try_select(self, other)
     }
 
+    /* TODO
     /// Joins the result of two futures, waiting for them both to complete.
     ///
     /// This function will return a new future which awaits both this and the
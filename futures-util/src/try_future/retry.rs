@@ -0,0 +1,317 @@
+//! Definition of the `Retry` combinator, running a fallible future again
+//! until it succeeds or a policy gives up.
+
+use crate::future::FutureExt;
+use crate::timer::{Clock, TimerHandle};
+use futures_core::future::{Future, TryFuture};
+use futures_core::task::{LocalWaker, Poll};
+use rand::Rng;
+use std::fmt;
+use std::pin::Pin;
+use std::time::Duration;
+
+/// Decides whether and when a failed attempt of a [`Retry`] future is
+/// retried.
+///
+/// This trait is implemented for closures taking the error and the number of
+/// attempts made so far, as well as for the [`ConstantBackoff`] and
+/// [`ExponentialBackoff`] policies.
+pub trait RetryPolicy<E> {
+    /// Called after attempt number `attempt` (starting at 1) failed with
+    /// `error`.
+    ///
+    /// Returns the time to wait before the next attempt, or `None` to give
+    /// up and resolve to `error`.
+    fn retry(&mut self, error: &E, attempt: usize) -> Option<Duration>;
+
+    /// Restricts this policy to errors for which `predicate` returns `true`.
+    /// Any other error is returned right away.
+    fn retry_if<F>(self, predicate: F) -> RetryIf<Self, F>
+        where F: FnMut(&E) -> bool,
+              Self: Sized
+    {
+        RetryIf { policy: self, predicate }
+    }
+}
+
+impl<E, F> RetryPolicy<E> for F
+    where F: FnMut(&E, usize) -> Option<Duration>
+{
+    fn retry(&mut self, error: &E, attempt: usize) -> Option<Duration> {
+        self(error, attempt)
+    }
+}
+
+/// Policy for the [`retry_if`](RetryPolicy::retry_if) method.
+#[derive(Debug, Clone)]
+pub struct RetryIf<P, F> {
+    policy: P,
+    predicate: F,
+}
+
+impl<E, P, F> RetryPolicy<E> for RetryIf<P, F>
+    where P: RetryPolicy<E>,
+          F: FnMut(&E) -> bool,
+{
+    fn retry(&mut self, error: &E, attempt: usize) -> Option<Duration> {
+        if (self.predicate)(error) {
+            self.policy.retry(error, attempt)
+        } else {
+            None
+        }
+    }
+}
+
+/// A policy which retries every error after the same delay.
+#[derive(Debug, Clone)]
+pub struct ConstantBackoff {
+    delay: Duration,
+    max_attempts: Option<usize>,
+}
+
+impl ConstantBackoff {
+    /// Creates a policy which waits `delay` before every retry and never
+    /// gives up.
+    pub fn new(delay: Duration) -> ConstantBackoff {
+        ConstantBackoff { delay, max_attempts: None }
+    }
+
+    /// Gives up once `max_attempts` attempts, including the first one, have
+    /// failed.
+    pub fn max_attempts(mut self, max_attempts: usize) -> ConstantBackoff {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+}
+
+impl<E> RetryPolicy<E> for ConstantBackoff {
+    fn retry(&mut self, _error: &E, attempt: usize) -> Option<Duration> {
+        if exhausted(self.max_attempts, attempt) {
+            return None;
+        }
+        Some(self.delay)
+    }
+}
+
+/// A policy which retries every error, multiplying the delay by a constant
+/// factor after every attempt.
+#[derive(Debug, Clone)]
+pub struct ExponentialBackoff {
+    initial: Duration,
+    factor: f64,
+    max_delay: Option<Duration>,
+    jitter: bool,
+    max_attempts: Option<usize>,
+}
+
+impl ExponentialBackoff {
+    /// Creates a policy which waits `initial` before the first retry and
+    /// doubles the delay for every following retry, without ever giving up.
+    pub fn new(initial: Duration) -> ExponentialBackoff {
+        ExponentialBackoff {
+            initial,
+            factor: 2.0,
+            max_delay: None,
+            jitter: false,
+            max_attempts: None,
+        }
+    }
+
+    /// Sets the factor the delay is multiplied by after every attempt.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `factor` is less than 1.
+    pub fn factor(mut self, factor: f64) -> ExponentialBackoff {
+        assert!(factor >= 1.0, "backoff factor must be at least 1");
+        self.factor = factor;
+        self
+    }
+
+    /// Caps the delay between two attempts at `max_delay`.
+    pub fn max_delay(mut self, max_delay: Duration) -> ExponentialBackoff {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Randomizes each delay to lie between half of it and all of it, so
+    /// that many clients failing at the same time don't retry in lockstep.
+    pub fn jitter(mut self, jitter: bool) -> ExponentialBackoff {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Gives up once `max_attempts` attempts, including the first one, have
+    /// failed.
+    pub fn max_attempts(mut self, max_attempts: usize) -> ExponentialBackoff {
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+}
+
+impl<E> RetryPolicy<E> for ExponentialBackoff {
+    fn retry(&mut self, _error: &E, attempt: usize) -> Option<Duration> {
+        if exhausted(self.max_attempts, attempt) {
+            return None;
+        }
+
+        let exp = attempt.saturating_sub(1).min(i32::max_value() as usize) as i32;
+        let mut nanos = to_nanos(self.initial) * self.factor.powi(exp);
+        if let Some(max_delay) = self.max_delay {
+            nanos = nanos.min(to_nanos(max_delay));
+        }
+        if self.jitter && nanos >= 2.0 {
+            nanos = rand::thread_rng().gen_range(nanos / 2.0, nanos);
+        }
+        Some(from_nanos(nanos))
+    }
+}
+
+fn exhausted(max_attempts: Option<usize>, attempt: usize) -> bool {
+    match max_attempts {
+        Some(max_attempts) => attempt >= max_attempts,
+        None => false,
+    }
+}
+
+fn to_nanos(dur: Duration) -> f64 {
+    dur.as_secs() as f64 * 1e9 + f64::from(dur.subsec_nanos())
+}
+
+fn from_nanos(nanos: f64) -> Duration {
+    if nanos >= u64::max_value() as f64 * 1e9 {
+        return Duration::from_secs(u64::max_value());
+    }
+    Duration::new((nanos / 1e9) as u64, (nanos % 1e9) as u32)
+}
+
+/// Future for the [`retry`] function.
+#[must_use = "futures do nothing unless polled"]
+pub struct Retry<F, Fut, P, C: Clock> {
+    factory: F,
+    policy: P,
+    clock: C,
+    attempt: usize,
+    state: State<Fut, C::Delay>,
+}
+
+enum State<Fut, D> {
+    // No attempt has been started yet.
+    Start,
+    Running(Fut),
+    Waiting(D),
+    Done,
+}
+
+impl<F, Fut, P, C: Clock> fmt::Debug for Retry<F, Fut, P, C> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Retry")
+            .field("attempt", &self.attempt)
+            .finish()
+    }
+}
+
+impl<F, Fut: Unpin, P, C: Clock> Unpin for Retry<F, Fut, P, C> {}
+
+/// Creates a future which runs the future returned by `factory` until it
+/// succeeds, retrying failed attempts as decided by `policy`.
+///
+/// The returned future resolves to the output of the first successful
+/// attempt, or to the error of the last attempt once `policy` gives up.
+/// Delays between attempts are measured by the global timer, see
+/// [`retry_with_clock`] to use a different one.
+///
+/// This function is only available when the `std` feature of this library
+/// is activated, and it is activated by default.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, retry, ExponentialBackoff};
+/// use std::time::Duration;
+///
+/// let mut attempts = 0;
+/// let policy = ExponentialBackoff::new(Duration::from_millis(1)).max_attempts(5);
+/// let fut = retry(|| {
+///     attempts += 1;
+///     future::ready(if attempts < 3 { Err(attempts) } else { Ok(attempts) })
+/// }, policy);
+///
+/// assert_eq!(block_on(fut), Ok(3));
+/// ```
+pub fn retry<F, Fut, P>(factory: F, policy: P) -> Retry<F, Fut, P, TimerHandle>
+    where F: FnMut() -> Fut,
+          Fut: TryFuture,
+          P: RetryPolicy<Fut::Error>,
+{
+    retry_with_clock(factory, policy, TimerHandle::default())
+}
+
+/// Like [`retry`], but measures the delays between attempts with `clock`.
+pub fn retry_with_clock<F, Fut, P, C>(
+    factory: F,
+    policy: P,
+    clock: C,
+) -> Retry<F, Fut, P, C>
+    where F: FnMut() -> Fut,
+          Fut: TryFuture,
+          P: RetryPolicy<Fut::Error>,
+          C: Clock,
+{
+    Retry { factory, policy, clock, attempt: 0, state: State::Start }
+}
+
+impl<F, Fut, P, C> Future for Retry<F, Fut, P, C>
+    where F: FnMut() -> Fut,
+          Fut: TryFuture,
+          P: RetryPolicy<Fut::Error>,
+          C: Clock,
+{
+    type Output = Result<Fut::Ok, Fut::Error>;
+
+    fn poll(self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        // Safety: `Fut` is never moved out of `state`, it is only dropped in
+        // place when the state is replaced.
+        let this = unsafe { Pin::get_unchecked_mut(self) };
+
+        loop {
+            let delay = match &mut this.state {
+                State::Start => {
+                    this.state = State::Running((this.factory)());
+                    continue;
+                }
+                State::Running(fut) => {
+                    let fut = unsafe { Pin::new_unchecked(fut) };
+                    let error = match ready!(fut.try_poll(lw)) {
+                        Ok(output) => {
+                            this.state = State::Done;
+                            return Poll::Ready(Ok(output));
+                        }
+                        Err(error) => error,
+                    };
+                    this.attempt += 1;
+                    match this.policy.retry(&error, this.attempt) {
+                        Some(delay) => delay,
+                        None => {
+                            this.state = State::Done;
+                            return Poll::Ready(Err(error));
+                        }
+                    }
+                }
+                State::Waiting(delay) => {
+                    ready!(delay.poll_unpin(lw));
+                    this.state = State::Running((this.factory)());
+                    continue;
+                }
+                State::Done => panic!("Retry polled after completion"),
+            };
+
+            this.state = if delay == Duration::from_secs(0) {
+                State::Running((this.factory)())
+            } else {
+                State::Waiting(this.clock.delay_at(this.clock.now() + delay))
+            };
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! Definition of the `SelectOk` combinator, finding the first successful future
+//! in a list.
+
+use std::fmt;
+use std::mem;
+use std::pin::Pin;
+use std::prelude::v1::*;
+
+use futures_core::future::{Future, FusedFuture, TryFuture};
+use futures_core::task::{LocalWaker, Poll};
+
+/// Future for the [`select_ok`] function, waiting for one of any of a list of
+/// futures to successfully complete. Unlike `select_all`, this future ignores
+/// all but the last error, if there are any.
+#[must_use = "futures do nothing unless polled"]
+pub struct SelectOk<Fut> {
+    inner: Vec<Fut>,
+}
+
+impl<Fut: fmt::Debug> fmt::Debug for SelectOk<Fut> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SelectOk")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<Fut: Unpin> Unpin for SelectOk<Fut> {}
+
+/// Creates a new future which will select the first successful future over a
+/// list of futures.
+///
+/// The returned future will wait for any future within `iter` to be ready and
+/// Ok. Unlike `select_all`, this will only return the first successful
+/// completion, or the last failure. This is useful in contexts where any
+/// success is desired and failures are ignored, unless all the futures fail.
+///
+/// The futures must be [`Unpin`], as the remaining ones are handed back to the
+/// caller. Futures that are not `Unpin`, such as the futures returned by
+/// `async fn`s, can be used by boxing them with [`Box::pin`] first.
+///
+/// # Panics
+///
+/// This function will panic if the iterator specified contains no items.
+pub fn select_ok<I>(iter: I) -> SelectOk<I::Item>
+    where I: IntoIterator,
+          I::Item: TryFuture + Unpin,
+{
+    let ret = SelectOk {
+        inner: iter.into_iter().collect()
+    };
+    assert!(!ret.inner.is_empty());
+    ret
+}
+
+impl<Fut: TryFuture + Unpin> FusedFuture for SelectOk<Fut> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<Fut: TryFuture + Unpin> Future for SelectOk<Fut> {
+    type Output = Result<(Fut::Ok, Vec<Fut>), Fut::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        // loop until we've either exhausted all errors, a success was hit, or nothing is ready
+        loop {
+            let item = self.inner.iter_mut().enumerate().filter_map(|(i, f)| {
+                match Pin::new(f).try_poll(lw) {
+                    Poll::Pending => None,
+                    Poll::Ready(e) => Some((i, e)),
+                }
+            }).next();
+            match item {
+                Some((idx, res)) => {
+                    // always remove Ok or Err, if it's not the last Err continue looping
+                    drop(self.inner.remove(idx));
+                    match res {
+                        Ok(e) => {
+                            let rest = mem::replace(&mut self.inner, Vec::new());
+                            return Poll::Ready(Ok((e, rest)))
+                        }
+                        Err(e) => {
+                            if self.inner.is_empty() {
+                                return Poll::Ready(Err(e))
+                            }
+                        }
+                    }
+                }
+                None => {
+                    // based on the filter above, nothing is ready, return
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Definition of the `TryJoinAll` combinator, waiting for all of a list of
 //! futures to finish with either success or error.
 
+use crate::stream::{FuturesUnordered, OrderWrapper, StreamExt};
 use std::fmt;
 use std::future::Future;
 use std::iter::FromIterator;
@@ -8,82 +9,61 @@ use std::mem;
 use std::pin::Pin;
 use std::prelude::v1::*;
 use std::task::Poll;
+use std::vec;
 
-use super::TryFuture;
-
-#[derive(Debug)]
-enum ElemState<F>
-where
-    F: TryFuture,
-{
-    Pending(F),
-    Done(Option<F::Ok>),
-}
-
-impl<F> ElemState<F>
-where
-    F: TryFuture,
-{
-    fn pending_pin_mut<'a>(self: Pin<&'a mut Self>) -> Option<Pin<&'a mut F>> {
-        // Safety: Basic enum pin projection, no drop + optionally Unpin based
-        // on the type of this variant
-        match unsafe { self.get_unchecked_mut() } {
-            ElemState::Pending(f) => Some(unsafe { Pin::new_unchecked(f) }),
-            ElemState::Done(_) => None,
-        }
-    }
-
-    fn take_done(self: Pin<&mut Self>) -> Option<F::Ok> {
-        // Safety: Going from pin to a variant we never pin-project
-        match unsafe { self.get_unchecked_mut() } {
-            ElemState::Pending(_) => None,
-            ElemState::Done(output) => output.take(),
-        }
-    }
-}
-
-impl<F> Unpin for ElemState<F> where F: TryFuture + Unpin {}
-
-fn iter_pin_mut<T>(slice: Pin<&mut [T]>) -> impl Iterator<Item = Pin<&mut T>> {
-    // Safety: `std` _could_ make this unsound if it were to decide Pin's
-    // invariants aren't required to transmit through slices. Otherwise this has
-    // the same safety as a normal field pin projection.
-    unsafe { slice.get_unchecked_mut() }
-        .iter_mut()
-        .map(|t| unsafe { Pin::new_unchecked(t) })
-}
-
-enum FinalState<E = ()> {
-    Pending,
-    AllDone,
-    Error(E)
-}
+use super::{IntoFuture, TryFuture};
 
 /// A future which takes a list of futures and resolves with a vector of the
 /// completed values or an error.
 ///
-/// This future is created with the `try_join_all` function.
+/// This future is created with the `try_join_all` or `try_join_all_limited`
+/// functions.
 #[must_use = "futures do nothing unless polled"]
 pub struct TryJoinAll<F>
 where
     F: TryFuture,
 {
-    elems: Pin<Box<[ElemState<F>]>>,
+    // Futures which haven't been started yet.
+    queued: vec::IntoIter<F>,
+    in_progress: FuturesUnordered<OrderWrapper<IntoFuture<F>>>,
+    outputs: Vec<Option<F::Ok>>,
+    limit: usize,
+    next_index: usize,
 }
 
+// Queued futures are moved into `in_progress` before they are pinned, which
+// keeps them behind a `Box`.
+impl<F: TryFuture> Unpin for TryJoinAll<F> {}
+
 impl<F> fmt::Debug for TryJoinAll<F>
 where
-    F: TryFuture + fmt::Debug,
-    F::Ok: fmt::Debug,
-    F::Error: fmt::Debug,
+    F: TryFuture,
 {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("TryJoinAll")
-            .field("elems", &self.elems)
+            .field("queued", &self.queued.len())
+            .field("in_progress", &self.in_progress.len())
+            .field("limit", &self.limit)
             .finish()
     }
 }
 
+impl<F: TryFuture> TryJoinAll<F> {
+    fn new<I>(i: I, limit: usize) -> TryJoinAll<F>
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let queued: Vec<F> = i.into_iter().collect();
+        TryJoinAll {
+            outputs: queued.iter().map(|_| None).collect(),
+            queued: queued.into_iter(),
+            in_progress: FuturesUnordered::new(),
+            limit,
+            next_index: 0,
+        }
+    }
+}
+
 /// Creates a future which represents either a collection of the results of the
 /// futures given or an error.
 ///
@@ -125,10 +105,44 @@ where
     I: IntoIterator,
     I::Item: TryFuture,
 {
-    let elems: Box<[_]> = i.into_iter().map(ElemState::Pending).collect();
-    TryJoinAll {
-        elems: Box::into_pin(elems),
-    }
+    TryJoinAll::new(i, usize::max_value())
+}
+
+/// Creates a future which represents either a collection of the results of the
+/// futures given or an error, running at most `limit` of the futures at the
+/// same time.
+///
+/// This works like [`try_join_all`], except that the futures are started in
+/// the order they were provided and a future is only started once fewer than
+/// `limit` futures are in progress. Futures which haven't been started when
+/// an error occurs are never started.
+///
+/// # Panics
+///
+/// This function panics if `limit` is zero.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(async_await, await_macro, futures_api)]
+/// # futures::executor::block_on(async {
+/// use futures::future::{self, try_join_all_limited};
+///
+/// let futures = (1..=100).map(future::ok::<u32, u32>);
+///
+/// assert_eq!(
+///     await!(try_join_all_limited(futures, 10)),
+///     Ok((1..=100).collect::<Vec<_>>()),
+/// );
+/// # });
+/// ```
+pub fn try_join_all_limited<I>(i: I, limit: usize) -> TryJoinAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: TryFuture,
+{
+    assert!(limit > 0, "limit must be greater than zero");
+    TryJoinAll::new(i, limit)
 }
 
 impl<F> Future for TryJoinAll<F>
@@ -141,36 +155,40 @@ where
         mut self: Pin<&mut Self>,
         lw: &::std::task::LocalWaker,
     ) -> Poll<Self::Output> {
-        let mut state = FinalState::AllDone;
-
-        for mut elem in iter_pin_mut(self.elems.as_mut()) {
-            if let Some(pending) = elem.as_mut().pending_pin_mut() {
-                match pending.try_poll(lw) {
-                    Poll::Pending => state = FinalState::Pending,
-                    Poll::Ready(output) => match output {
-                        Ok(item) => elem.set(ElemState::Done(Some(item))),
-                        Err(e) => {
-                            state = FinalState::Error(e);
-                            break;
-                        }
+        let this = &mut *self;
+
+        loop {
+            while this.in_progress.len() < this.limit {
+                match this.queued.next() {
+                    Some(future) => {
+                        let index = this.next_index;
+                        this.next_index += 1;
+                        let data = IntoFuture::new(future);
+                        this.in_progress.push(OrderWrapper { data, index });
                     }
+                    None => break,
                 }
             }
-        }
 
-        match state {
-            FinalState::Pending => Poll::Pending,
-            FinalState::AllDone => {
-                let mut elems = mem::replace(&mut self.elems, Box::pin([]));
-                let results = iter_pin_mut(elems.as_mut())
-                    .map(|e| e.take_done().unwrap())
-                    .collect();
-                Poll::Ready(Ok(results))
-            },
-            FinalState::Error(e) => {
-                let _ = mem::replace(&mut self.elems, Box::pin([]));
-                Poll::Ready(Err(e))
-            },
+            match ready!(this.in_progress.poll_next_unpin(lw)) {
+                Some(OrderWrapper { data: Ok(item), index }) => {
+                    this.outputs[index] = Some(item);
+                }
+                Some(OrderWrapper { data: Err(e), .. }) => {
+                    // Cancel all other futures.
+                    this.queued = Vec::new().into_iter();
+                    this.in_progress = FuturesUnordered::new();
+                    this.outputs = Vec::new();
+                    return Poll::Ready(Err(e))
+                }
+                None => {
+                    let outputs = mem::replace(&mut this.outputs, Vec::new());
+                    let results = outputs.into_iter()
+                        .map(|output| output.unwrap())
+                        .collect();
+                    return Poll::Ready(Ok(results))
+                }
+            }
         }
     }
 }
@@ -0,0 +1,94 @@
+use core::pin::Pin;
+use either::Either;
+use futures_core::future::{Future, FusedFuture, TryFuture};
+use futures_core::task::{LocalWaker, Poll};
+
+/// Future for the [`try_select()`] function.
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub struct TrySelect<A, B> {
+    inner: Option<(A, B)>,
+}
+
+impl<A: Unpin, B: Unpin> Unpin for TrySelect<A, B> {}
+
+type EitherOk<A, B> = Either<(<A as TryFuture>::Ok, B), (<B as TryFuture>::Ok, A)>;
+type EitherErr<A, B> = Either<(<A as TryFuture>::Error, B), (<B as TryFuture>::Error, A)>;
+
+/// Waits for either one of two differently-typed futures to complete.
+///
+/// This function will return a new future which awaits for either one of both
+/// futures to complete. The returned future will finish with both the value
+/// resolved and a future representing the completion of the other work.
+/// Both the first success and the first error complete the returned future,
+/// with `Ok` and `Err` respectively.
+///
+/// Note that this function consumes the receiving futures and returns a
+/// wrapped version of them.
+///
+/// Every time the returned future is polled, `future1` is polled before
+/// `future2`, so if both are ready the result of `future1` is returned;
+/// [`try_select_biased`] is an alias which makes that priority explicit.
+/// Both futures must be [`Unpin`], as the unfinished future is handed back to
+/// the caller.
+///
+/// # Examples
+///
+/// ```
+/// use futures::executor::block_on;
+/// use futures::future::{self, Either};
+///
+/// let a = future::empty::<Result<i32, &str>>();
+/// let b = future::err::<i32, &str>("boom");
+///
+/// match block_on(future::try_select(a, b)) {
+///     Err(Either::Right((error, _a))) => assert_eq!(error, "boom"),
+///     _ => unreachable!(),
+/// }
+/// ```
+pub fn try_select<A, B>(future1: A, future2: B) -> TrySelect<A, B>
+    where A: TryFuture + Unpin, B: TryFuture + Unpin
+{
+    TrySelect { inner: Some((future1, future2)) }
+}
+
+/// Waits for either one of two differently-typed futures to complete, always
+/// polling the first future first.
+///
+/// This is the same as [`try_select()`], which already polls `future1` before
+/// `future2`. Using this name documents that the priority is relied upon.
+pub fn try_select_biased<A, B>(future1: A, future2: B) -> TrySelect<A, B>
+    where A: TryFuture + Unpin, B: TryFuture + Unpin
+{
+    try_select(future1, future2)
+}
+
+impl<A, B> FusedFuture for TrySelect<A, B> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<A, B> Future for TrySelect<A, B>
+where
+    A: TryFuture + Unpin,
+    B: TryFuture + Unpin,
+{
+    type Output = Result<EitherOk<A, B>, EitherErr<A, B>>;
+
+    fn poll(mut self: Pin<&mut Self>, lw: &LocalWaker) -> Poll<Self::Output> {
+        let (mut a, mut b) = self.inner.take().expect("cannot poll TrySelect twice");
+        match Pin::new(&mut a).try_poll(lw) {
+            Poll::Ready(Ok(x)) => Poll::Ready(Ok(Either::Left((x, b)))),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(Either::Left((e, b)))),
+            Poll::Pending => match Pin::new(&mut b).try_poll(lw) {
+                Poll::Ready(Ok(x)) => Poll::Ready(Ok(Either::Right((x, a)))),
+                Poll::Ready(Err(e)) => Poll::Ready(Err(Either::Right((e, a)))),
+                Poll::Pending => {
+                    self.inner = Some((a, b));
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
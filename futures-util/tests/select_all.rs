@@ -31,3 +31,45 @@ fn is_terminated() {
     assert_eq!(tasks.poll_next_unpin(lw), Poll::Ready(None));
     assert_eq!(tasks.is_terminated(), true);
 }
+
+#[test]
+fn biased_prefers_earlier_streams() {
+    use futures::stream;
+    use futures::executor::block_on;
+
+    let tasks = futures::stream::select_all_biased(vec![
+        stream::iter(vec![1, 2]),
+        stream::iter(vec![3, 4]),
+        stream::iter(vec![5, 6]),
+    ]);
+    assert_eq!(block_on(tasks.collect::<Vec<_>>()), vec![1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn round_robin_alternates_streams() {
+    use futures::stream;
+    use futures::executor::block_on;
+
+    let mut tasks = SelectAll::round_robin();
+    tasks.push(stream::iter(vec![1, 2, 3]));
+    tasks.push(stream::iter(vec![4]));
+    tasks.push(stream::iter(vec![5, 6]));
+    assert_eq!(block_on(tasks.collect::<Vec<_>>()), vec![1, 4, 5, 2, 6, 3]);
+}
+
+#[test]
+fn biased_is_terminated() {
+    let lw = noop_local_waker_ref();
+    let mut tasks = SelectAll::biased();
+
+    assert_eq!(tasks.is_terminated(), false);
+    assert_eq!(tasks.poll_next_unpin(lw), Poll::Ready(None));
+    assert_eq!(tasks.is_terminated(), true);
+
+    tasks.push(future::ready(1).into_stream());
+
+    assert_eq!(tasks.is_terminated(), false);
+    assert_eq!(tasks.poll_next_unpin(lw), Poll::Ready(Some(1)));
+    assert_eq!(tasks.poll_next_unpin(lw), Poll::Ready(None));
+    assert_eq!(tasks.is_terminated(), true);
+}
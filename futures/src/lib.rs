@@ -65,15 +65,25 @@ pub mod channel {
     //! Cross-task communication.
     //!
     //! Like threads, concurrent tasks sometimes need to communicate with each
-    //! other. This module contains two basic abstractions for doing so:
+    //! other. This module contains a few basic abstractions for doing so:
     //!
     //! - [oneshot](crate::channel::oneshot), a way of sending a single value
     //!   from one task to another.
     //! - [mpsc](crate::channel::mpsc), a multi-producer, single-consumer
     //!   channel for sending values between tasks, analogous to the
     //!   similarly-named structure in the standard library.
-
-    pub use futures_channel::{oneshot, mpsc};
+    //! - [priority](crate::channel::priority), an mpsc channel with
+    //!   multiple priority lanes, where higher-priority values overtake
+    //!   lower-priority ones.
+    //! - [mpmc](crate::channel::mpmc), a multi-producer, multi-consumer
+    //!   channel which delivers each value to exactly one receiver.
+    //! - [broadcast](crate::channel::broadcast), a multi-producer,
+    //!   multi-consumer channel which delivers every value to every
+    //!   receiver.
+    //! - [watch](crate::channel::watch), a channel holding a single value
+    //!   which notifies receivers whenever it changes.
+
+    pub use futures_channel::{oneshot, mpsc, priority, mpmc, broadcast, watch};
 }
 
 #[cfg(feature = "compat")]
@@ -175,15 +185,21 @@ pub mod future {
         FutureObj, LocalFutureObj, UnsafeFutureObj,
     };
 
+    #[cfg(feature = "std")]
+    pub use futures_core::future::{BoxFuture, LocalBoxFuture};
+
     pub use futures_util::future::{
         empty, Empty,
         lazy, Lazy,
         maybe_done, MaybeDone,
         poll_fn, PollFn,
+        poll_immediate, PollImmediate,
         ready, ok, err, Ready,
 
         OptionFuture,
 
+        select, select_biased, Select, Either,
+
         FutureExt,
         FlattenStream, Flatten, Fuse, Inspect, IntoStream, Join, Join3, Join4,
         Join5, Map, Then,
@@ -192,13 +208,14 @@ pub mod future {
     #[cfg(feature = "std")]
     pub use futures_util::future::{
         abortable, Abortable, AbortHandle, AbortRegistration, Aborted,
+        CancellationToken, Cancelled,
         Remote, RemoteHandle,
         // For FutureExt:
-        CatchUnwind, Shared,
+        CatchUnwind, Shared, WeakShared, Timeout,
 
-        join_all, JoinAll,
+        join_all, join_all_limited, JoinAll,
 
-        // ToDo: SelectAll, SelectOk, select_all, select_ok
+        select_all, SelectAll,
     };
 
     pub use futures_util::try_future::{
@@ -207,6 +224,18 @@ pub mod future {
         UnwrapOrElse,
         TryJoin, TryJoin3, TryJoin4, TryJoin5,
         try_join_all, TryJoinAll,
+        try_select, try_select_biased, TrySelect,
+    };
+
+    #[cfg(feature = "std")]
+    pub use futures_util::try_future::try_join_all_limited;
+
+    #[cfg(feature = "std")]
+    pub use futures_util::try_future::{
+        select_ok, SelectOk,
+
+        retry, retry_with_clock, Retry, RetryPolicy, RetryIf, ConstantBackoff,
+        ExponentialBackoff,
     };
 }
 
@@ -276,6 +305,9 @@ pub mod sink {
 
     pub use futures_sink::Sink;
 
+    #[cfg(feature = "std")]
+    pub use futures_sink::{BoxSink, LocalBoxSink};
+
     pub use futures_util::sink::{
         Close, Flush, Send, SendAll, SinkErrInto, SinkMapErr, With,
         SinkExt, Fanout, Drain, DrainError, drain,
@@ -305,18 +337,24 @@ pub mod stream {
         StreamObj, LocalStreamObj, UnsafeStreamObj,
     };
 
+    #[cfg(feature = "std")]
+    pub use futures_core::stream::{BoxStream, LocalBoxStream};
+
     pub use futures_util::stream::{
         iter, Iter,
         repeat, Repeat,
         empty, Empty,
         once, Once,
         poll_fn, PollFn,
+        poll_immediate, PollImmediate,
         unfold, Unfold,
+        select_with_strategy, PollNext, SelectWithStrategy,
 
         StreamExt,
-        Chain, Concat, Filter, FilterMap, Flatten, Fold, Forward, ForEach, Fuse,
-        StreamFuture, Inspect, Map, Next, Peekable, Select, Skip, SkipWhile,
-        Take, TakeWhile, Then, Zip
+        Chain, Concat, Dedup, DedupByKey, Filter, FilterMap, Flatten, Fold,
+        Forward, ForEach, Fuse,
+        StreamFuture, Inspect, Interleave, Map, Next, Peekable, Select, Skip,
+        SkipWhile, Take, TakeWhile, Then, Unzip, Zip, ZipLongest, EitherOrBoth,
     };
 
     #[cfg(feature = "std")]
@@ -326,9 +364,11 @@ pub mod stream {
 
         // For StreamExt:
         BufferUnordered, Buffered, CatchUnwind, Chunks, Collect, SplitStream,
-        SplitSink, ReuniteError,
+        SplitSink, ReuniteError, FlattenUnordered, FlatMapUnordered,
+        Broadcast, LagPolicy, Lagged, ChunkBy,
+        ChunksTimeout, Debounce, Throttle, Timeout,
 
-        select_all, SelectAll,
+        select_all, select_all_biased, SelectAll,
     };
 
     pub use futures_util::try_stream::{
@@ -385,6 +425,24 @@ pub mod task {
     pub use futures_util::task::AtomicWaker;
 }
 
+#[cfg(feature = "std")]
+pub mod timer {
+    //! Timers driven independently of any executor.
+    //!
+    //! This module contains:
+    //!
+    //! - [`Delay`](crate::timer::Delay), a future which completes at a point
+    //!   in time.
+    //! - [`Interval`](crate::timer::Interval), a stream which yields at a
+    //!   fixed period.
+    //! - [`Timer`](crate::timer::Timer), the timer wheel backing them, which
+    //!   can follow the system clock or a manually advanced mock clock.
+
+    pub use futures_util::timer::{
+        Clock, Delay, Elapsed, Interval, Timer, TimerHandle,
+    };
+}
+
 // `select!` re-export --------------------------------------
 
 #[cfg(feature = "std")]
@@ -1,8 +1,9 @@
 #![feature(futures_api)]
 
-use futures::channel::oneshot;
+use futures::channel::{mpsc, oneshot};
 use futures::executor::block_on;
-use futures::future::{abortable, Aborted, FutureExt};
+use futures::future::{self, abortable, Abortable, Aborted, CancellationToken, FutureExt};
+use futures::stream::StreamExt;
 use futures::task::Poll;
 use futures_test::task::new_count_waker;
 
@@ -38,3 +39,88 @@ fn abortable_resolves() {
 
     assert_eq!(Ok(Ok(())), block_on(abortable_rx));
 }
+
+#[test]
+fn abortable_stream_terminates() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let (mut stream, abort_handle) = abortable(rx);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(block_on(stream.next()), Some(1));
+    assert!(!abort_handle.is_aborted());
+
+    let (lw, counter) = new_count_waker();
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(&lw));
+    abort_handle.abort();
+    assert_eq!(counter, 1);
+    assert!(abort_handle.is_aborted());
+    assert!(stream.is_aborted());
+
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(&lw));
+}
+
+#[test]
+fn cancellation_token_cancels_children() {
+    let parent = CancellationToken::new();
+    let child = parent.child_token();
+    let grandchild = child.child_token();
+    let sibling = parent.child_token();
+
+    let (_tx, rx) = oneshot::channel::<()>();
+    let mut fut = Abortable::new(rx, grandchild.abort_registration());
+    let (_stream_tx, stream_rx) = mpsc::unbounded::<()>();
+    let mut stream = Abortable::new(stream_rx, grandchild.abort_registration());
+    let mut cancelled = parent.cancelled();
+
+    let (lw, counter) = new_count_waker();
+    assert_eq!(Poll::Pending, fut.poll_unpin(&lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(&lw));
+    assert_eq!(Poll::Pending, cancelled.poll_unpin(&lw));
+
+    child.cancel();
+    assert_eq!(counter, 2);
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+    assert!(!parent.is_cancelled());
+    assert!(!sibling.is_cancelled());
+    assert_eq!(Poll::Ready(Err(Aborted)), fut.poll_unpin(&lw));
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(&lw));
+    assert_eq!(Poll::Pending, cancelled.poll_unpin(&lw));
+
+    parent.cancel();
+    assert_eq!(counter, 3);
+    assert!(sibling.is_cancelled());
+    assert_eq!(Poll::Ready(()), cancelled.poll_unpin(&lw));
+}
+
+#[test]
+fn cancellation_token_child_of_cancelled_parent() {
+    let parent = CancellationToken::new();
+    parent.cancel();
+
+    let child = parent.child_token();
+    assert!(child.is_cancelled());
+    let fut = Abortable::new(future::empty::<()>(), child.abort_registration());
+    assert_eq!(Err(Aborted), block_on(fut));
+    block_on(child.cancelled());
+}
+
+#[test]
+fn cancellation_token_survives_dropped_intermediate_tokens() {
+    let root = CancellationToken::new();
+    let fut = Abortable::new(future::empty::<()>(), root.child_token().abort_registration());
+
+    let middle = root.child_token();
+    let leaf = middle.child_token();
+    drop(middle);
+
+    drop(root.cancelled());
+    let cancelled = root.cancelled();
+
+    root.cancel();
+    assert!(leaf.is_cancelled());
+    assert_eq!(Err(Aborted), block_on(fut));
+    block_on(leaf.cancelled());
+    block_on(cancelled);
+}
@@ -0,0 +1,80 @@
+#![feature(futures_api)]
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::future::{self, BoxFuture, FutureExt, FutureObj, LocalBoxFuture};
+use futures::sink::{BoxSink, LocalBoxSink, SinkExt};
+use futures::stream::{self, BoxStream, LocalBoxStream, StreamExt};
+use std::rc::Rc;
+
+trait Answer {
+    fn answer(&self) -> BoxFuture<'_, i32>;
+}
+
+struct Ready;
+struct Mapped(i32);
+
+impl Answer for Ready {
+    fn answer(&self) -> BoxFuture<'_, i32> {
+        future::ready(42).boxed()
+    }
+}
+
+impl Answer for Mapped {
+    fn answer(&self) -> BoxFuture<'_, i32> {
+        future::ready(self.0).map(|x| x * 2).boxed()
+    }
+}
+
+#[test]
+fn boxed_erases_future_type() {
+    let answers: Vec<Box<dyn Answer>> = vec![Box::new(Ready), Box::new(Mapped(21))];
+    for answer in &answers {
+        assert_eq!(block_on(answer.answer()), 42);
+    }
+}
+
+#[test]
+fn boxed_local_accepts_non_send_future() {
+    let rc = Rc::new(5);
+    let fut: LocalBoxFuture<'_, i32> = future::ready(rc).map(|rc| *rc).boxed_local();
+    assert_eq!(block_on(fut), 5);
+}
+
+#[test]
+fn boxed_future_into_future_obj() {
+    let fut = FutureObj::new(future::ready(1).boxed());
+    assert_eq!(block_on(fut), 1);
+}
+
+#[test]
+fn boxed_streams() {
+    let streams: Vec<BoxStream<'_, i32>> = vec![
+        stream::iter(vec![1, 2]).boxed(),
+        stream::once(future::ready(3)).boxed(),
+    ];
+    let all = block_on(stream::iter(streams).flatten().collect::<Vec<_>>());
+    assert_eq!(all, vec![1, 2, 3]);
+
+    let rc = Rc::new(4);
+    let local: LocalBoxStream<'_, i32> = stream::iter(vec![rc]).map(|rc| *rc).boxed_local();
+    assert_eq!(block_on(local.collect::<Vec<_>>()), vec![4]);
+}
+
+#[test]
+fn boxed_sink() {
+    let (tx, rx) = mpsc::channel::<i32>(1);
+    let mut sink: BoxSink<'_, i32, mpsc::SendError> = tx.boxed();
+    block_on(sink.send(1)).unwrap();
+    drop(sink);
+    assert_eq!(block_on(rx.collect::<Vec<_>>()), vec![1]);
+}
+
+#[test]
+fn boxed_local_sink() {
+    let (tx, rx) = mpsc::unbounded::<Rc<i32>>();
+    let mut sink: LocalBoxSink<'_, Rc<i32>, mpsc::SendError> = tx.boxed_local();
+    block_on(sink.send(Rc::new(1))).unwrap();
+    drop(sink);
+    assert_eq!(block_on(rx.map(|rc| *rc).collect::<Vec<_>>()), vec![1]);
+}
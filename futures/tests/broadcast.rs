@@ -0,0 +1,113 @@
+#![feature(futures_api)]
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::stream::{self, FusedStream, LagPolicy, Lagged, StreamExt};
+use futures::stream::Broadcast;
+use futures::task::{local_waker_from_nonlocal, Poll, Wake};
+use futures_test::task::noop_local_waker_ref;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn every_subscriber_sees_every_item() {
+    let stream = stream::iter(1..=5).broadcast(8, LagPolicy::DropOldest);
+    let subscribers = (0..4).map(|_| stream.clone()).collect::<Vec<_>>();
+    drop(stream);
+
+    let join_handles = subscribers.into_iter()
+        .map(|subscriber| thread::spawn(move || block_on(subscriber.collect::<Vec<_>>())))
+        .collect::<Vec<_>>();
+
+    for join_handle in join_handles {
+        assert_eq!(join_handle.join().unwrap(), vec![Ok(1), Ok(2), Ok(3), Ok(4), Ok(5)]);
+    }
+}
+
+#[test]
+fn subscribe_skips_buffered_items() {
+    let lw = noop_local_waker_ref();
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut first = rx.broadcast(4, LagPolicy::DropOldest);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(first.poll_next_unpin(lw), Poll::Ready(Some(Ok(1))));
+
+    let mut clone = first.clone();
+    let mut late = first.subscribe();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(late.poll_next_unpin(lw), Poll::Ready(Some(Ok(2))));
+    assert_eq!(clone.poll_next_unpin(lw), Poll::Ready(Some(Ok(2))));
+    assert_eq!(first.poll_next_unpin(lw), Poll::Ready(Some(Ok(2))));
+    assert_eq!(first.poll_next_unpin(lw), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(first.poll_next_unpin(lw), Poll::Ready(None));
+    assert!(first.is_terminated());
+    assert_eq!(late.poll_next_unpin(lw), Poll::Ready(None));
+}
+
+#[test]
+fn drop_oldest_reports_lag() {
+    let lw = noop_local_waker_ref();
+    let mut fast = stream::iter(1..=5).broadcast(2, LagPolicy::DropOldest);
+    let mut slow = fast.clone();
+
+    assert_eq!(block_on(fast.by_ref().collect::<Vec<_>>()),
+               vec![Ok(1), Ok(2), Ok(3), Ok(4), Ok(5)]);
+
+    assert_eq!(slow.poll_next_unpin(lw), Poll::Ready(Some(Err(Lagged(3)))));
+    assert_eq!(slow.poll_next_unpin(lw), Poll::Ready(Some(Ok(4))));
+    assert_eq!(slow.poll_next_unpin(lw), Poll::Ready(Some(Ok(5))));
+    assert_eq!(slow.poll_next_unpin(lw), Poll::Ready(None));
+}
+
+#[test]
+fn backpressure_waits_for_slowest() {
+    let lw = noop_local_waker_ref();
+    let mut fast = stream::iter(1..=3).broadcast(2, LagPolicy::Backpressure);
+    let mut slow = fast.clone();
+
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Ready(Some(Ok(1))));
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Ready(Some(Ok(2))));
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Pending);
+
+    assert_eq!(slow.poll_next_unpin(lw), Poll::Ready(Some(Ok(1))));
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Ready(Some(Ok(3))));
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Pending);
+
+    assert_eq!(block_on(slow.collect::<Vec<_>>()), vec![Ok(2), Ok(3)]);
+    assert_eq!(fast.poll_next_unpin(lw), Poll::Ready(None));
+}
+
+#[test]
+fn subscriber_can_be_polled_from_its_waker() {
+    // Polls the subscriber as soon as it is woken, like an executor running
+    // the task inline would.
+    struct PollOnWake {
+        subscriber: Mutex<Broadcast<mpsc::UnboundedReceiver<i32>>>,
+        items: Mutex<Vec<Poll<Option<Result<i32, Lagged>>>>>,
+    }
+
+    impl Wake for PollOnWake {
+        fn wake(arc_self: &Arc<Self>) {
+            let item = arc_self.subscriber.lock().unwrap()
+                .poll_next_unpin(noop_local_waker_ref());
+            arc_self.items.lock().unwrap().push(item);
+        }
+    }
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut first = rx.broadcast(4, LagPolicy::DropOldest);
+    let poll_on_wake = Arc::new(PollOnWake {
+        subscriber: Mutex::new(first.clone()),
+        items: Mutex::new(Vec::new()),
+    });
+
+    let lw = local_waker_from_nonlocal(poll_on_wake.clone());
+    assert_eq!(poll_on_wake.subscriber.lock().unwrap().poll_next_unpin(&lw), Poll::Pending);
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(*poll_on_wake.items.lock().unwrap(), vec![Poll::Ready(Some(Ok(1)))]);
+    assert_eq!(first.poll_next_unpin(noop_local_waker_ref()), Poll::Ready(Some(Ok(1))));
+}
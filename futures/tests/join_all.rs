@@ -43,3 +43,46 @@ fn join_all_from_iter() {
         vec![1, 2],
     )
 }
+
+#[test]
+fn join_all_limited_preserves_order() {
+    assert_done(
+        || Box::new(join_all_limited((0..100).map(ready), 7)),
+        (0..100).collect::<Vec<_>>(),
+    )
+}
+
+#[test]
+fn join_all_limited_runs_at_most_limit() {
+    use futures::channel::oneshot;
+    use futures_test::task::noop_local_waker_ref;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let started = Rc::new(Cell::new(0));
+    let (txs, rxs): (Vec<_>, Vec<_>) = (0..4).map(|_| oneshot::channel::<i32>()).unzip();
+    let futures = rxs.into_iter().map(|rx| {
+        let started = started.clone();
+        lazy(move |_| started.set(started.get() + 1)).then(move |()| rx)
+    });
+
+    let mut fut = join_all_limited(futures, 2);
+    let lw = noop_local_waker_ref();
+    assert!(fut.poll_unpin(lw).is_pending());
+    assert_eq!(started.get(), 2);
+
+    let mut txs = txs.into_iter();
+    let (tx0, tx1) = (txs.next().unwrap(), txs.next().unwrap());
+    tx1.send(1).unwrap();
+    assert!(fut.poll_unpin(lw).is_pending());
+    assert_eq!(started.get(), 3);
+    tx0.send(0).unwrap();
+    assert!(fut.poll_unpin(lw).is_pending());
+    assert_eq!(started.get(), 4);
+
+    for (i, tx) in txs.enumerate() {
+        tx.send(i as i32 + 2).unwrap();
+    }
+    let outputs = block_on(fut).into_iter().map(Result::unwrap).collect::<Vec<_>>();
+    assert_eq!(outputs, vec![0, 1, 2, 3]);
+}
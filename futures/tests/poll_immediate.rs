@@ -0,0 +1,42 @@
+#![feature(futures_api)]
+
+use futures::channel::{mpsc, oneshot};
+use futures::executor::block_on;
+use futures::future::{self, poll_immediate, FutureExt};
+use futures::stream::{self, StreamExt};
+
+#[test]
+fn now_or_never() {
+    assert_eq!(future::ready(1).now_or_never(), Some(1));
+    assert_eq!(future::empty::<i32>().now_or_never(), None);
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    let shared = rx.shared();
+    assert_eq!(shared.clone().now_or_never(), None);
+    tx.send(2).unwrap();
+    assert_eq!(shared.now_or_never(), Some(Ok(2)));
+}
+
+#[test]
+fn poll_immediate_future() {
+    assert_eq!(block_on(poll_immediate(future::ready(1))), Some(1));
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    assert_eq!(block_on(poll_immediate(rx)), None);
+    drop(tx);
+}
+
+#[test]
+fn poll_immediate_stream() {
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut ready = stream::poll_immediate(rx);
+    assert_eq!(block_on(ready.next()), None);
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(block_on(ready.by_ref().collect::<Vec<_>>()), vec![1, 2]);
+
+    tx.unbounded_send(3).unwrap();
+    drop(tx);
+    assert_eq!(block_on(ready.collect::<Vec<_>>()), vec![3]);
+}
@@ -0,0 +1,103 @@
+#![feature(futures_api)]
+
+use futures::executor::block_on;
+use futures::future::{
+    self, retry, retry_with_clock, ConstantBackoff, ExponentialBackoff, FutureExt,
+    RetryPolicy,
+};
+use futures::task::Poll;
+use futures::timer::Timer;
+use futures_test::task::noop_local_waker_ref;
+use std::cell::Cell;
+use std::time::Duration;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn retries_until_success() {
+    let mut attempts = 0;
+    let fut = retry(|| {
+        attempts += 1;
+        future::ready(if attempts < 3 { Err(attempts) } else { Ok(attempts) })
+    }, ConstantBackoff::new(ms(0)));
+
+    assert_eq!(block_on(fut), Ok(3));
+}
+
+#[test]
+fn first_attempt_starts_on_first_poll() {
+    let attempts = Cell::new(0);
+    let mut fut = retry(|| {
+        attempts.set(attempts.get() + 1);
+        future::ready(Ok::<_, ()>(()))
+    }, ConstantBackoff::new(ms(0)));
+
+    assert_eq!(attempts.get(), 0);
+    assert_eq!(fut.poll_unpin(noop_local_waker_ref()), Poll::Ready(Ok(())));
+    assert_eq!(attempts.get(), 1);
+}
+
+#[test]
+fn returns_last_error_when_exhausted() {
+    let mut attempts = 0;
+    let fut = retry(|| {
+        attempts += 1;
+        future::ready(Err::<(), _>(attempts))
+    }, ConstantBackoff::new(ms(1)).max_attempts(4));
+
+    assert_eq!(block_on(fut), Err(4));
+}
+
+#[test]
+fn retry_if_gives_up_on_other_errors() {
+    let mut attempts = 0;
+    let policy = ConstantBackoff::new(ms(0)).retry_if(|e: &&str| *e == "transient");
+    let fut = retry(|| {
+        attempts += 1;
+        future::ready(Err::<(), _>(if attempts < 3 { "transient" } else { "fatal" }))
+    }, policy);
+
+    assert_eq!(block_on(fut), Err("fatal"));
+}
+
+#[test]
+fn exponential_backoff_delays() {
+    let mut policy = ExponentialBackoff::new(ms(10))
+        .max_delay(ms(50))
+        .max_attempts(6);
+
+    let delays: Vec<_> = (1..=6).map(|attempt| policy.retry(&(), attempt)).collect();
+    assert_eq!(delays, vec![
+        Some(ms(10)), Some(ms(20)), Some(ms(40)), Some(ms(50)), Some(ms(50)), None,
+    ]);
+
+    let mut policy = ExponentialBackoff::new(ms(10)).jitter(true);
+    for attempt in 1..10 {
+        let delay = RetryPolicy::<()>::retry(&mut policy, &(), attempt).unwrap();
+        let full = ms(10) * 2u32.pow(attempt as u32 - 1);
+        assert!(delay >= full / 2 && delay <= full);
+    }
+}
+
+#[test]
+fn waits_between_attempts() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let mut attempts = 0;
+    let policy = |_: &(), attempt: usize| Some(ms(10 * attempt as u64));
+    let mut fut = retry_with_clock(|| {
+        attempts += 1;
+        future::ready(if attempts < 3 { Err(()) } else { Ok(attempts) })
+    }, policy, timer.handle());
+
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    timer.advance(ms(19));
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    timer.advance(ms(1));
+    assert_eq!(Poll::Ready(Ok(3)), fut.poll_unpin(lw));
+}
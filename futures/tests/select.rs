@@ -0,0 +1,106 @@
+#![feature(async_await, await_macro, futures_api)]
+
+use futures::channel::oneshot;
+use futures::executor::block_on;
+use futures::future::{self, Either, FutureExt, TryFutureExt};
+use pin_utils::pin_mut;
+
+#[test]
+fn select_returns_other_future() {
+    let (tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+
+    tx2.send(2).unwrap();
+    let rx1 = match block_on(future::select(rx1, rx2)) {
+        Either::Left(_) => panic!("first future shouldn't be ready"),
+        Either::Right((value, rx1)) => {
+            assert_eq!(value, Ok(2));
+            rx1
+        }
+    };
+
+    tx1.send(1).unwrap();
+    assert_eq!(block_on(rx1), Ok(1));
+}
+
+#[test]
+fn select_pinned_async_fn() {
+    async fn one() -> i32 { 1 }
+
+    block_on(async {
+        let a = one();
+        let b = future::empty::<i32>();
+        pin_mut!(a);
+
+        match await!(future::select(a, b)) {
+            Either::Left((value, _)) => assert_eq!(value, 1),
+            Either::Right(_) => panic!("`empty` should never complete"),
+        }
+    });
+
+    let boxed = one().boxed();
+    match block_on(future::select(boxed, future::empty::<i32>())) {
+        Either::Left((value, _)) => assert_eq!(value, 1),
+        Either::Right(_) => panic!("`empty` should never complete"),
+    }
+}
+
+#[test]
+fn select_method() {
+    match block_on(future::ready(1).select(future::empty::<i32>())) {
+        Either::Left((value, _)) => assert_eq!(value, 1),
+        Either::Right(_) => panic!("`empty` should never complete"),
+    }
+}
+
+#[test]
+fn select_prefers_first_future() {
+    match block_on(future::select(future::ready(1), future::ready(2))) {
+        Either::Left((value, _)) => assert_eq!(value, 1),
+        Either::Right(_) => panic!("first future should win"),
+    }
+    match block_on(future::select_biased(future::ready(1), future::ready(2))) {
+        Either::Left((value, _)) => assert_eq!(value, 1),
+        Either::Right(_) => panic!("first future should win"),
+    }
+}
+
+#[test]
+fn try_select_first_ok() {
+    let (tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+
+    tx2.send(2).unwrap();
+    let rx1 = match block_on(future::try_select(rx1, rx2)) {
+        Ok(Either::Right((value, rx1))) => {
+            assert_eq!(value, 2);
+            rx1
+        }
+        _ => panic!("second future should succeed first"),
+    };
+
+    tx1.send(1).unwrap();
+    assert_eq!(block_on(rx1), Ok(1));
+}
+
+#[test]
+fn try_select_first_err() {
+    let a = future::empty::<Result<i32, &str>>();
+    let b = future::err::<i32, &str>("boom");
+
+    match block_on(TryFutureExt::select(a, b)) {
+        Err(Either::Right((error, _))) => assert_eq!(error, "boom"),
+        _ => panic!("second future should fail first"),
+    }
+}
+
+#[test]
+fn try_select_biased_prefers_first_future() {
+    let a = future::err::<i32, i32>(1);
+    let b = future::ok::<i32, i32>(2);
+
+    match block_on(future::try_select_biased(a, b)) {
+        Err(Either::Left((error, _))) => assert_eq!(error, 1),
+        _ => panic!("first future should win"),
+    }
+}
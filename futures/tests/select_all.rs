@@ -0,0 +1,42 @@
+#![feature(futures_api)]
+
+use futures::executor::block_on;
+use futures::future::{ready, select_all};
+
+#[test]
+fn smoke() {
+    let v = vec![
+        ready(1),
+        ready(2),
+        ready(3),
+    ];
+
+    let (i, idx, v) = block_on(select_all(v));
+    assert_eq!(i, 1);
+    assert_eq!(idx, 0);
+
+    let (i, idx, v) = block_on(select_all(v));
+    assert_eq!(i, 2);
+    assert_eq!(idx, 0);
+
+    let (i, idx, v) = block_on(select_all(v));
+    assert_eq!(i, 3);
+    assert_eq!(idx, 0);
+
+    assert!(v.is_empty());
+}
+
+#[test]
+fn pending_futures_are_returned() {
+    use futures::channel::oneshot;
+
+    let (_tx1, rx1) = oneshot::channel::<i32>();
+    let (tx2, rx2) = oneshot::channel::<i32>();
+    let (_tx3, rx3) = oneshot::channel::<i32>();
+    tx2.send(2).unwrap();
+
+    let (i, idx, v) = block_on(select_all(vec![rx1, rx2, rx3]));
+    assert_eq!(i, Ok(2));
+    assert_eq!(idx, 1);
+    assert_eq!(v.len(), 2);
+}
@@ -1,4 +1,7 @@
+#![feature(futures_api)]
+
 use futures::executor::block_on;
+use futures::future::{err, ok, select_ok};
 
 #[test]
 fn ignore_err() {
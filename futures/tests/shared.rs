@@ -3,7 +3,8 @@
 use futures::channel::oneshot;
 use futures::executor::{block_on, LocalPool};
 use futures::future::{self, FutureExt, LocalFutureObj};
-use futures::task::LocalSpawn;
+use futures::task::{LocalSpawn, Poll};
+use futures_test::task::noop_local_waker_ref;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::thread;
@@ -152,3 +153,33 @@ fn dont_do_unnecessary_clones_on_output() {
     assert_eq!(block_on(rx.clone()).unwrap().0.get(), 2);
     assert_eq!(block_on(rx).unwrap().0.get(), 2);
 }
+
+#[test]
+fn downgrade() {
+    let (tx, rx) = oneshot::channel::<i32>();
+    let shared = rx.shared();
+    let weak = shared.downgrade().unwrap();
+    assert_eq!(shared.strong_count(), Some(1));
+    assert_eq!(shared.weak_count(), Some(1));
+
+    let shared2 = weak.upgrade().unwrap();
+    assert!(shared.ptr_eq(&shared2));
+    assert!(!shared.ptr_eq(&oneshot::channel::<i32>().1.shared()));
+    assert_eq!(shared.strong_count(), Some(2));
+
+    tx.send(42).unwrap();
+    assert_eq!(block_on(shared).unwrap(), 42);
+    assert!(weak.upgrade().is_some());
+    assert_eq!(block_on(shared2).unwrap(), 42);
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn downgrade_after_completion() {
+    let (tx, rx) = oneshot::channel::<i32>();
+    let mut shared = rx.shared();
+    tx.send(1).unwrap();
+    assert_eq!(shared.poll_unpin(noop_local_waker_ref()), Poll::Ready(Ok(1)));
+    assert!(shared.downgrade().is_none());
+    assert_eq!(shared.strong_count(), None);
+}
@@ -20,3 +20,184 @@ fn select() {
     select_and_compare(vec![1, 2, 3], vec![4, 5], vec![1, 4, 2, 5, 3]);
     select_and_compare(vec![1, 2], vec![4, 5, 6], vec![1, 4, 2, 5, 6]);
 }
+
+#[test]
+fn select_with_strategy_biased() {
+    use futures::stream::{select_with_strategy, PollNext};
+
+    let a = stream::iter(vec![1, 2, 3]);
+    let b = stream::iter(vec![4, 5, 6]);
+    let biased = select_with_strategy(a, b, |_: &mut ()| PollNext::Left);
+    assert_eq!(block_on(biased.collect::<Vec<_>>()), vec![1, 2, 3, 4, 5, 6]);
+
+    let a = stream::iter(vec![1, 2, 3]);
+    let b = stream::iter(vec![4, 5, 6]);
+    let biased = select_with_strategy(a, b, |_: &mut ()| PollNext::Right);
+    assert_eq!(block_on(biased.collect::<Vec<_>>()), vec![4, 5, 6, 1, 2, 3]);
+}
+
+#[test]
+fn select_with_strategy_round_robin() {
+    use futures::stream::{select_with_strategy, PollNext};
+
+    let a = stream::iter(vec![1, 2, 3]);
+    let b = stream::iter(vec![4, 5]);
+    let rrobin = select_with_strategy(a, b, |last: &mut PollNext| last.toggle());
+    assert_eq!(block_on(rrobin.collect::<Vec<_>>()), vec![1, 4, 2, 5, 3]);
+}
+
+#[test]
+fn zip_longest() {
+    use futures::stream::EitherOrBoth::{Both, Left, Right};
+
+    let a = stream::iter(vec![1, 2]);
+    let b = stream::iter(vec![3, 4, 5]);
+    let vec = block_on(a.zip_longest(b).collect::<Vec<_>>());
+    assert_eq!(vec, vec![Both(1, 3), Both(2, 4), Right(5)]);
+
+    let a = stream::iter(vec![1, 2, 3]);
+    let b = stream::iter(Vec::<u32>::new());
+    let vec = block_on(a.zip_longest(b).collect::<Vec<_>>());
+    assert_eq!(vec, vec![Left(1), Left(2), Left(3)]);
+}
+
+#[test]
+fn interleave() {
+    fn interleave_and_compare(a: Vec<u32>, b: Vec<u32>, expected: Vec<u32>) {
+        let a = stream::iter(a);
+        let b = stream::iter(b);
+        let vec = block_on(a.interleave(b).collect::<Vec<_>>());
+        assert_eq!(vec, expected);
+    }
+
+    interleave_and_compare(vec![1, 2, 3], vec![4, 5, 6], vec![1, 4, 2, 5, 3, 6]);
+    interleave_and_compare(vec![1], vec![4, 5, 6], vec![1, 4, 5, 6]);
+    interleave_and_compare(vec![1, 2, 3], vec![], vec![1, 2, 3]);
+}
+
+#[test]
+fn interleave_waits_for_turn() {
+    use futures::channel::mpsc;
+    use futures::task::Poll;
+    use futures_test::task::noop_local_waker_ref;
+
+    let lw = noop_local_waker_ref();
+    let (tx1, rx1) = mpsc::unbounded();
+    let (tx2, rx2) = mpsc::unbounded();
+    let mut stream = rx1.interleave(rx2);
+
+    tx2.unbounded_send(2).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Pending);
+
+    tx1.unbounded_send(1).unwrap();
+    tx1.unbounded_send(3).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some(1)));
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some(2)));
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some(3)));
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Pending);
+}
+
+#[test]
+fn unzip() {
+    let stream = stream::iter(vec![(1, "a"), (2, "b"), (3, "c")]);
+    let (numbers, letters): (Vec<_>, Vec<_>) = block_on(stream.unzip());
+    assert_eq!(numbers, vec![1, 2, 3]);
+    assert_eq!(letters, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn flatten_unordered() {
+    let streams = stream::iter(vec![
+        stream::iter(vec![1, 2]),
+        stream::iter(vec![3, 4]),
+        stream::iter(vec![5]),
+    ]);
+    let mut vec = block_on(streams.flatten_unordered(None).collect::<Vec<_>>());
+    vec.sort();
+    assert_eq!(vec, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn flatten_unordered_limit() {
+    use futures::channel::mpsc;
+    use futures::task::Poll;
+    use futures_test::task::noop_local_waker_ref;
+
+    let lw = noop_local_waker_ref();
+    let (tx1, rx1) = mpsc::unbounded::<u32>();
+    let (tx2, rx2) = mpsc::unbounded::<u32>();
+    let mut stream = stream::iter(vec![rx1, rx2]).flatten_unordered(1);
+
+    // Only the first inner stream is polled until it completes.
+    tx2.unbounded_send(2).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Pending);
+
+    tx1.unbounded_send(1).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some(1)));
+
+    drop(tx1);
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some(2)));
+
+    drop(tx2);
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(None));
+}
+
+#[test]
+fn flat_map_unordered() {
+    let stream = stream::iter(1..=3)
+        .flat_map_unordered(None, |x| stream::iter(vec![x; x as usize]));
+    let mut vec = block_on(stream.collect::<Vec<_>>());
+    vec.sort();
+    assert_eq!(vec, vec![1, 2, 2, 3, 3, 3]);
+}
+
+#[test]
+fn chunk_by() {
+    let stream = stream::iter(vec!["a1", "a2", "b1", "a3", "c1", "c2"]);
+    let groups = block_on(stream.chunk_by(|s| s.as_bytes()[0]).collect::<Vec<_>>());
+    assert_eq!(groups, vec![
+        (b'a', vec!["a1", "a2"]),
+        (b'b', vec!["b1"]),
+        (b'a', vec!["a3"]),
+        (b'c', vec!["c1", "c2"]),
+    ]);
+
+    let empty = stream::iter(Vec::<u32>::new()).chunk_by(|x| *x);
+    assert_eq!(block_on(empty.collect::<Vec<_>>()), vec![]);
+}
+
+#[test]
+fn chunk_by_flushes_on_termination() {
+    use futures::channel::mpsc;
+    use futures::stream::FusedStream;
+    use futures::task::Poll;
+    use futures_test::task::noop_local_waker_ref;
+
+    let lw = noop_local_waker_ref();
+    let (tx, rx) = mpsc::unbounded();
+    let mut stream = rx.chunk_by(|x: &u32| *x / 10);
+
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Pending);
+
+    tx.unbounded_send(10).unwrap();
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some((0, vec![1, 2]))));
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Pending);
+
+    drop(tx);
+    assert_eq!(stream.is_terminated(), false);
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(Some((1, vec![10]))));
+    assert_eq!(stream.poll_next_unpin(lw), Poll::Ready(None));
+    assert_eq!(stream.is_terminated(), true);
+}
+
+#[test]
+fn dedup() {
+    let stream = stream::iter(vec![1, 1, 2, 2, 2, 3, 1, 1]);
+    assert_eq!(block_on(stream.dedup().collect::<Vec<_>>()), vec![1, 2, 3, 1]);
+
+    let stream = stream::iter(vec![(1, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+    let deduped = block_on(stream.dedup_by_key(|pair| pair.0).collect::<Vec<_>>());
+    assert_eq!(deduped, vec![(1, 'a'), (2, 'c'), (1, 'd')]);
+}
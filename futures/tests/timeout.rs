@@ -0,0 +1,117 @@
+#![feature(futures_api)]
+
+use futures::channel::{mpsc, oneshot};
+use futures::future::FutureExt;
+use futures::stream::{self, StreamExt};
+use futures::task::Poll;
+use futures::timer::{Elapsed, Timer};
+use futures_test::task::noop_local_waker_ref;
+use std::time::Duration;
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn future_timeout() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let (_tx, rx) = oneshot::channel::<i32>();
+    let mut fut = rx.timeout(ms(10), timer.handle());
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    timer.advance(ms(9));
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    timer.advance(ms(1));
+    assert_eq!(Poll::Ready(Err(Elapsed)), fut.poll_unpin(lw));
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    let mut fut = rx.timeout(ms(10), timer.handle());
+    assert_eq!(Poll::Pending, fut.poll_unpin(lw));
+    tx.send(1).unwrap();
+    assert_eq!(Poll::Ready(Ok(Ok(1))), fut.poll_unpin(lw));
+}
+
+#[test]
+fn stream_timeout() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut stream = rx.timeout(ms(10), timer.handle());
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(Err(Elapsed))), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+
+    tx.unbounded_send(1).unwrap();
+    assert_eq!(Poll::Ready(Some(Ok(1))), stream.poll_next_unpin(lw));
+    drop(tx);
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(lw));
+}
+
+#[test]
+fn throttle() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let mut stream = stream::iter(1..=3).throttle(ms(10), timer.handle());
+    assert_eq!(Poll::Ready(Some(1)), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(2)), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(3)), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(lw));
+}
+
+#[test]
+fn debounce() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut stream = rx.debounce(ms(10), timer.handle());
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+
+    timer.advance(ms(5));
+    tx.unbounded_send(3).unwrap();
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(5));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(5));
+    assert_eq!(Poll::Ready(Some(3)), stream.poll_next_unpin(lw));
+
+    tx.unbounded_send(4).unwrap();
+    drop(tx);
+    assert_eq!(Poll::Ready(Some(4)), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(lw));
+}
+
+#[test]
+fn chunks_timeout() {
+    let timer = Timer::new_mock();
+    let lw = noop_local_waker_ref();
+
+    let (tx, rx) = mpsc::unbounded::<i32>();
+    let mut stream = rx.chunks_timeout(3, ms(10), timer.handle());
+    tx.unbounded_send(1).unwrap();
+    tx.unbounded_send(2).unwrap();
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(vec![1, 2])), stream.poll_next_unpin(lw));
+
+    for i in 3..=6 {
+        tx.unbounded_send(i).unwrap();
+    }
+    assert_eq!(Poll::Ready(Some(vec![3, 4, 5])), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, stream.poll_next_unpin(lw));
+    drop(tx);
+    assert_eq!(Poll::Ready(Some(vec![6])), stream.poll_next_unpin(lw));
+    assert_eq!(Poll::Ready(None), stream.poll_next_unpin(lw));
+}
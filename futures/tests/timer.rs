@@ -0,0 +1,140 @@
+#![feature(futures_api)]
+
+use futures::executor::{block_on, LocalPool};
+use futures::future::FutureExt;
+use futures::stream::StreamExt;
+use futures::task::Poll;
+use futures::timer::{Delay, Interval, Timer};
+use futures_test::task::{new_count_waker, noop_local_waker_ref};
+use std::time::{Duration, Instant};
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn delay_completes_on_advance() {
+    let timer = Timer::new_mock();
+    let mut delay = timer.handle().delay(ms(50));
+
+    let (lw, counter) = new_count_waker();
+    assert_eq!(Poll::Pending, delay.poll_unpin(&lw));
+
+    timer.advance(ms(49));
+    assert_eq!(counter, 0);
+    assert_eq!(Poll::Pending, delay.poll_unpin(&lw));
+
+    timer.advance(ms(1));
+    assert_eq!(counter, 1);
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(&lw));
+}
+
+#[test]
+fn delay_in_the_past_is_ready() {
+    let timer = Timer::new_mock();
+    let handle = timer.handle();
+    let now = handle.now();
+    timer.advance(ms(10));
+
+    let mut delay = handle.delay_at(now);
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(noop_local_waker_ref()));
+}
+
+#[test]
+fn delays_fire_in_order_across_levels() {
+    let timer = Timer::new_mock();
+    let handle = timer.handle();
+    let durations = [ms(3), ms(70), ms(5_000), ms(300_000), ms(20_000_000)];
+    let mut delays: Vec<_> = durations.iter().map(|d| handle.delay(*d)).collect();
+
+    let lw = noop_local_waker_ref();
+    for delay in &mut delays {
+        assert_eq!(Poll::Pending, delay.poll_unpin(lw));
+    }
+
+    let mut elapsed = ms(0);
+    for (i, dur) in durations.iter().enumerate() {
+        timer.advance(*dur - ms(1) - elapsed);
+        assert_eq!(Poll::Pending, delays[i].poll_unpin(lw));
+        timer.advance(ms(1));
+        assert_eq!(Poll::Ready(()), delays[i].poll_unpin(lw));
+        for delay in &mut delays[i + 1..] {
+            assert_eq!(Poll::Pending, delay.poll_unpin(lw));
+        }
+        elapsed = *dur;
+    }
+}
+
+#[test]
+fn delay_reset() {
+    let timer = Timer::new_mock();
+    let handle = timer.handle();
+    let mut delay = handle.delay(ms(10));
+
+    let lw = noop_local_waker_ref();
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(lw));
+
+    delay.reset(handle.now() + ms(10));
+    assert_eq!(Poll::Pending, delay.poll_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(lw));
+}
+
+#[test]
+fn delay_reset_before_deadline() {
+    let timer = Timer::new_mock();
+    let handle = timer.handle();
+    let mut delay = handle.delay(ms(10));
+
+    let lw = noop_local_waker_ref();
+    assert_eq!(Poll::Pending, delay.poll_unpin(lw));
+    for _ in 0..100 {
+        delay.reset(handle.now() + ms(30));
+    }
+    timer.advance(ms(10));
+    assert_eq!(Poll::Pending, delay.poll_unpin(lw));
+    timer.advance(ms(20));
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(lw));
+
+    delay.reset(handle.now() + ms(100));
+    delay.reset(handle.now() + ms(5));
+    timer.advance(ms(5));
+    assert_eq!(Poll::Ready(()), delay.poll_unpin(lw));
+}
+
+#[test]
+fn interval_ticks() {
+    let timer = Timer::new_mock();
+    let mut interval = timer.handle().interval(ms(10));
+
+    let lw = noop_local_waker_ref();
+    assert_eq!(Poll::Pending, interval.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(())), interval.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, interval.poll_next_unpin(lw));
+    timer.advance(ms(10));
+    assert_eq!(Poll::Ready(Some(())), interval.poll_next_unpin(lw));
+
+    // Missed ticks are skipped.
+    timer.advance(ms(35));
+    assert_eq!(Poll::Ready(Some(())), interval.poll_next_unpin(lw));
+    assert_eq!(Poll::Pending, interval.poll_next_unpin(lw));
+    timer.advance(ms(5));
+    assert_eq!(Poll::Ready(Some(())), interval.poll_next_unpin(lw));
+}
+
+#[test]
+fn real_delay() {
+    let start = Instant::now();
+    block_on(Delay::new(ms(20)));
+    assert!(start.elapsed() >= ms(20));
+}
+
+#[test]
+fn real_interval_on_local_pool() {
+    let start = Instant::now();
+    let mut pool = LocalPool::new();
+    pool.run_until(Interval::new(ms(5)).take(3).collect::<Vec<_>>());
+    assert!(start.elapsed() >= ms(15));
+}
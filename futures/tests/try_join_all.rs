@@ -1,7 +1,7 @@
 #![feature(async_await, futures_api)]
 
 use futures_util::future::*;
-use futures_util::try_future::{try_join_all, TryJoinAll};
+use futures_util::try_future::{try_join_all, try_join_all_limited, TryJoinAll};
 use std::future::Future;
 use futures::executor::block_on;
 use std::fmt::Debug;
@@ -45,3 +45,15 @@ fn try_join_all_from_iter() {
         Ok::<_, usize>(vec![1, 2]),
     )
 }
+
+#[test]
+fn try_join_all_limited_collects() {
+    assert_done(
+        || Box::new(try_join_all_limited((0..100).map(ok), 7)),
+        Ok::<_, usize>((0..100).collect::<Vec<_>>()),
+    );
+    assert_done(
+        || Box::new(try_join_all_limited(vec![ok(1), err(2), ok(3)], 1)),
+        Err(2),
+    );
+}